@@ -1,26 +1,53 @@
-use sp_core::{Pair, Public, sr25519};
+use std::{convert::TryFrom, env, fs};
+use sp_core::{crypto::Ss58Codec, Pair, Public, sr25519};
 use node_template_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig,
-	SudoConfig, SystemConfig, WASM_BINARY, Signature
+	AccountId, AssetId, AssetsConfig, Balance, BalancesConfig, BlockNumber, CouncilConfig, DemocracyConfig,
+	ElectionsConfig, GenesisConfig, GrandpaConfig, IndicesConfig, SessionConfig, StakingConfig, StakerStatus,
+	SudoConfig, SystemConfig, TechnicalCommitteeConfig, TechnicalMembershipConfig, VestingConfig,
+	WASM_BINARY, Signature, Perbill, DAYS, SS58Prefix, TOKEN_DECIMALS, TOKEN_SYMBOL,
+	opaque::SessionKeys,
 };
-use sp_consensus_aura::sr25519::AuthorityId as AuraId;
+#[cfg(not(feature = "babe"))]
+use node_template_runtime::AuraConfig;
+#[cfg(feature = "babe")]
+use node_template_runtime::BabeConfig;
+/// The session key of the block authoring pallet the node is built with.
+#[cfg(not(feature = "babe"))]
+pub use sp_consensus_aura::sr25519::AuthorityId as ConsensusId;
+/// The session key of the block authoring pallet the node is built with.
+#[cfg(feature = "babe")]
+pub use sp_consensus_babe::AuthorityId as ConsensusId;
 use sp_finality_grandpa::AuthorityId as GrandpaId;
 use sp_runtime::traits::{Verify, IdentifyAccount};
-use sc_service::ChainType;
+use sc_service::{ChainType, Properties, config::MultiaddrWithPeerId};
+use sc_telemetry::TelemetryEndpoints;
 
 // The URL for the telemetry server.
-// const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
+const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
+/// The protocol ID of the staging network, keeping its nodes apart from other chains.
+const STAGING_PROTOCOL_ID: &str = "ntpl-staging";
+/// The number of authorities of the staging network.
+const STAGING_AUTHORITIES: usize = 3;
+
+pub use node_template_runtime::genesis::{get_from_seed, STASH};
+use node_template_runtime::genesis::ENDOWMENT;
+
+/// The LTK token, formerly the `LTKToken` ERC-20 on Moonbeam.
+const LTK: AssetId = 0;
+/// The LTK units initially held by the root key, i.e. a million tokens of 18 decimals.
+const LTK_SUPPLY: Balance = 1_000_000 * 1_000_000_000_000_000_000;
+
+/// A genesis vesting schedule: the account, the block the vesting starts at, the number of blocks
+/// it lasts and the part of the endowment that is liquid from the start.
+pub type VestingSchedule = (AccountId, BlockNumber, BlockNumber, Balance);
+
+/// The stash and controller accounts, and the block authoring and GRANDPA session keys of a
+/// genesis authority.
+pub type AuthorityKeys = (AccountId, AccountId, ConsensusId, GrandpaId);
 
 /// Specialized `ChainSpec`. This is a specialization of the general Substrate ChainSpec type.
 pub type ChainSpec = sc_service::GenericChainSpec<GenesisConfig>;
 
-/// Generate a crypto pair from seed.
-pub fn get_from_seed<TPublic: Public>(seed: &str) -> <TPublic::Pair as Pair>::Public {
-	TPublic::Pair::from_string(&format!("//{}", seed), None)
-		.expect("static values are valid; qed")
-		.public()
-}
-
 type AccountPublic = <Signature as Verify>::Signer;
 
 /// Generate an account ID from seed.
@@ -30,14 +57,126 @@ pub fn get_account_id_from_seed<TPublic: Public>(seed: &str) -> AccountId where
 	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
 
-/// Generate an Aura authority key.
-pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
+/// Generate the stash and controller accounts, and the block authoring (Aura or BABE) and GRANDPA
+/// session keys of an authority.
+pub fn authority_keys_from_seed(s: &str) -> AuthorityKeys {
 	(
-		get_from_seed::<AuraId>(s),
+		get_account_id_from_seed::<sr25519::Public>(&format!("{}//stash", s)),
+		get_account_id_from_seed::<sr25519::Public>(s),
+		get_from_seed::<ConsensusId>(s),
 		get_from_seed::<GrandpaId>(s),
 	)
 }
 
+/// Generate the keys of an authority from any secret URI, the way `authority_keys_from_seed`
+/// does from the seed of a well-known account: `authority_keys_from_suri("//Alice")` equals
+/// `authority_keys_from_seed("Alice")`.
+pub fn authority_keys_from_suri(suri: &str) -> Result<AuthorityKeys, String> {
+	fn public<TPublic: Public>(suri: &str) -> Result<<TPublic::Pair as Pair>::Public, String> {
+		TPublic::Pair::from_string(suri, None)
+			.map(|pair| pair.public())
+			.map_err(|e| format!("Invalid secret URI: {:?}", e))
+	}
+
+	Ok((
+		AccountPublic::from(public::<sr25519::Public>(&format!("{}//stash", suri))?).into_account(),
+		AccountPublic::from(public::<sr25519::Public>(suri)?).into_account(),
+		public::<ConsensusId>(suri)?,
+		public::<GrandpaId>(suri)?,
+	))
+}
+
+/// Parse a 32-byte account or public key, given as an SS58 address or in hex.
+fn raw_public(s: &str) -> Result<[u8; 32], String> {
+	let bytes = if s.starts_with("0x") {
+		sp_core::bytes::from_hex(s).map_err(|e| format!("Invalid hex key `{}`: {:?}", s, e))?
+	} else {
+		AccountId::from_ss58check(s)
+			.map_err(|e| format!("Invalid SS58 address `{}`: {:?}", s, e))?
+			.as_ref()
+			.to_vec()
+	};
+	<[u8; 32]>::try_from(&bytes[..]).map_err(|_| format!("`{}` is not 32 bytes long", s))
+}
+
+/// Parse a public key, given as an SS58 address or in hex.
+pub fn parse_public<P: Public>(s: &str) -> Result<P, String> {
+	raw_public(s).map(|bytes| P::from_slice(&bytes))
+}
+
+/// Parse an account ID, given as an SS58 address or in hex.
+pub fn parse_account_id(s: &str) -> Result<AccountId, String> {
+	raw_public(s).map(AccountId::from)
+}
+
+/// Parse an account, given as an SS58 address, in hex or as the secret URI of an sr25519 key.
+pub fn parse_account(s: &str) -> Result<AccountId, String> {
+	parse_account_id(s).or_else(|_| {
+		sr25519::Pair::from_string(s, None)
+			.map(|pair| AccountPublic::from(pair.public()).into_account())
+			.map_err(|_| format!("`{}` is neither an account nor a secret URI", s))
+	})
+}
+
+#[cfg(not(feature = "babe"))]
+fn session_keys(aura: ConsensusId, grandpa: GrandpaId) -> SessionKeys {
+	SessionKeys { aura, grandpa }
+}
+
+#[cfg(feature = "babe")]
+fn session_keys(babe: ConsensusId, grandpa: GrandpaId) -> SessionKeys {
+	SessionKeys { babe, grandpa }
+}
+
+// Moving from Aura to BABE: the presets below describe the same chains whatever the node is built
+// with, but a BABE build has a different runtime, session keys and genesis, hence a different
+// genesis hash. An existing Aura chain cannot switch to BABE by a runtime upgrade, as its nodes
+// would stop agreeing on who may author blocks; start a new chain instead, e.g. from a state
+// exported from the Aura one. BABE chains get their own IDs, so that their databases and
+// networks are kept apart from the Aura ones, and their validators must insert `babe` keys in
+// place of their `aura` keys, or rotate their session keys with `author_rotateKeys`.
+
+/// The properties wallets read the native token and the address format of the chains from.
+pub fn properties() -> Properties {
+	let mut properties = Properties::new();
+	properties.insert("tokenSymbol".into(), TOKEN_SYMBOL.into());
+	properties.insert("tokenDecimals".into(), TOKEN_DECIMALS.into());
+	properties.insert("ss58Format".into(), SS58Prefix::get().into());
+	properties
+}
+
+/// The ID of a preset chain, suffixed for BABE builds.
+fn chain_id(id: &str) -> String {
+	if cfg!(feature = "babe") {
+		format!("{}_babe", id)
+	} else {
+		id.to_string()
+	}
+}
+
+/// The genesis of the development chain, validated by Alice who is also the sudo account.
+pub fn development_genesis(wasm_binary: &[u8]) -> GenesisConfig {
+	testnet_genesis(
+		wasm_binary,
+		// Initial validators
+		vec![
+			authority_keys_from_seed("Alice"),
+		],
+		// Sudo account
+		get_account_id_from_seed::<sr25519::Public>("Alice"),
+		// Pre-funded accounts
+		vec![
+			get_account_id_from_seed::<sr25519::Public>("Alice"),
+			get_account_id_from_seed::<sr25519::Public>("Bob"),
+			get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
+			get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
+		],
+		// Vesting schedules
+		vec![],
+		true,
+	)
+}
+
 pub fn development_config() -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or("Development wasm binary not available".to_string())?;
 
@@ -45,25 +184,9 @@ pub fn development_config() -> Result<ChainSpec, String> {
 		// Name
 		"Development",
 		// ID
-		"dev",
+		&chain_id("dev"),
 		ChainType::Development,
-		move || testnet_genesis(
-			wasm_binary,
-			// Initial PoA authorities
-			vec![
-				authority_keys_from_seed("Alice"),
-			],
-			// Sudo account
-			get_account_id_from_seed::<sr25519::Public>("Alice"),
-			// Pre-funded accounts
-			vec![
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
-				get_account_id_from_seed::<sr25519::Public>("Bob"),
-				get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-			],
-			true,
-		),
+		move || development_genesis(wasm_binary),
 		// Bootnodes
 		vec![],
 		// Telemetry
@@ -71,7 +194,7 @@ pub fn development_config() -> Result<ChainSpec, String> {
 		// Protocol ID
 		None,
 		// Properties
-		None,
+		Some(properties()),
 		// Extensions
 		None,
 	))
@@ -84,11 +207,11 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 		// Name
 		"Local Testnet",
 		// ID
-		"local_testnet",
+		&chain_id("local_testnet"),
 		ChainType::Local,
 		move || testnet_genesis(
 			wasm_binary,
-			// Initial PoA authorities
+			// Initial validators
 			vec![
 				authority_keys_from_seed("Alice"),
 				authority_keys_from_seed("Bob"),
@@ -110,6 +233,14 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 				get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 				get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 			],
+			// Vesting schedules: the stashes of the non-validators vest over a year, a tenth of
+			// them being liquid from the start.
+			["Charlie", "Dave", "Eve", "Ferdie"].iter().map(|s| (
+				get_account_id_from_seed::<sr25519::Public>(&format!("{}//stash", s)),
+				0,
+				365 * DAYS,
+				ENDOWMENT / 10,
+			)).collect(),
 			true,
 		),
 		// Bootnodes
@@ -119,19 +250,163 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 		// Protocol ID
 		None,
 		// Properties
+		Some(properties()),
+		// Extensions
 		None,
+	))
+}
+
+/// Read a setting of the staging network from the `name` environment variable, or from the file
+/// named by the `<name>_FILE` one.
+fn staging_setting(name: &str) -> Result<String, String> {
+	match (env::var(name), env::var(format!("{}_FILE", name))) {
+		(Ok(value), _) => Ok(value),
+		(Err(_), Ok(path)) => fs::read_to_string(&path)
+			.map_err(|e| format!("Cannot read {} from {}: {}", name, path, e)),
+		(Err(_), Err(_)) => Err(format!("Neither {} nor {}_FILE is set", name, name)),
+	}
+}
+
+/// Read the public keys of the `n`-th staging authority: its stash and controller accounts, and its
+/// block authoring and GRANDPA keys, as SS58 addresses or in hex separated by whitespace.
+fn staging_authority(n: usize) -> Result<AuthorityKeys, String> {
+	let name = format!("STAGING_AUTHORITY_{}", n);
+	let setting = staging_setting(&name)?;
+	match setting.split_whitespace().collect::<Vec<_>>()[..] {
+		[stash, controller, authoring, grandpa] => Ok((
+			parse_account_id(stash)?,
+			parse_account_id(controller)?,
+			parse_public(authoring)?,
+			parse_public(grandpa)?,
+		)),
+		_ => Err(format!(
+			"{} must list the stash, controller, authoring and GRANDPA keys of the authority",
+			name,
+		)),
+	}.map_err(|e| format!("{}: {}", name, e))
+}
+
+/// A small private network of `STAGING_AUTHORITIES` authorities, reporting to telemetry. Its keys
+/// and boot nodes are read when the spec is loaded, so that no secret nor address is committed to
+/// the repository, and every missing or invalid setting is reported before the node starts:
+///
+/// - `STAGING_AUTHORITY_<n>` for `n` from 1, the public keys of an authority, see
+///   `staging_authority`;
+/// - `STAGING_SUDO`, the sudo account;
+/// - `STAGING_BOOT_NODES`, the addresses of the boot nodes, ending with their peer IDs and
+///   separated by whitespace.
+///
+/// Each of them can instead be read from the file named by the variable suffixed with `_FILE`.
+pub fn staging_config() -> Result<ChainSpec, String> {
+	let wasm_binary = WASM_BINARY.ok_or("Staging wasm binary not available".to_string())?;
+
+	let mut errors = Vec::new();
+	let mut authorities = Vec::new();
+	for n in 1..=STAGING_AUTHORITIES {
+		match staging_authority(n) {
+			Ok(keys) => authorities.push(keys),
+			Err(e) => errors.push(e),
+		}
+	}
+	let sudo = staging_setting("STAGING_SUDO")
+		.and_then(|sudo| parse_account_id(sudo.trim()))
+		.map_err(|e| errors.push(format!("STAGING_SUDO: {}", e)))
+		.ok();
+	let boot_nodes = staging_setting("STAGING_BOOT_NODES")
+		.and_then(|nodes| nodes.split_whitespace()
+			.map(|node| node.parse::<MultiaddrWithPeerId>()
+				.map_err(|e| format!("Invalid boot node `{}`: {}", node, e)))
+			.collect::<Result<Vec<_>, _>>())
+		.and_then(|nodes| if nodes.is_empty() {
+			Err("No boot node".to_string())
+		} else {
+			Ok(nodes)
+		})
+		.map_err(|e| errors.push(format!("STAGING_BOOT_NODES: {}", e)))
+		.ok();
+
+	// Sharing keys would let one validator equivocate for another.
+	for (i, a) in authorities.iter().enumerate() {
+		for b in &authorities[i + 1..] {
+			if a.0 == b.0 || a.1 == b.1 || a.2 == b.2 || a.3 == b.3 {
+				errors.push(format!("The staging authorities {} and {} share keys", a.0, b.0));
+			}
+		}
+	}
+
+	let (sudo, boot_nodes) = match (sudo, boot_nodes) {
+		(Some(sudo), Some(boot_nodes)) if errors.is_empty() => (sudo, boot_nodes),
+		_ => return Err(format!("Invalid staging network settings:\n{}", errors.join("\n"))),
+	};
+
+	let mut endowed_accounts = vec![sudo.clone()];
+	for (stash, controller, ..) in &authorities {
+		endowed_accounts.push(stash.clone());
+		endowed_accounts.push(controller.clone());
+	}
+	endowed_accounts.sort();
+	endowed_accounts.dedup();
+
+	Ok(ChainSpec::from_genesis(
+		// Name
+		"Staging Testnet",
+		// ID
+		&chain_id("staging_testnet"),
+		ChainType::Live,
+		move || testnet_genesis(
+			wasm_binary,
+			// Initial validators
+			authorities.clone(),
+			// Sudo account
+			sudo.clone(),
+			// Pre-funded accounts
+			endowed_accounts.clone(),
+			// Vesting schedules
+			vec![],
+			false,
+		),
+		// Bootnodes
+		boot_nodes,
+		// Telemetry
+		Some(TelemetryEndpoints::new(vec![(STAGING_TELEMETRY_URL.to_string(), 0)])
+			.expect("Staging telemetry url is valid; qed")),
+		// Protocol ID
+		Some(STAGING_PROTOCOL_ID),
+		// Properties
+		Some(properties()),
 		// Extensions
 		None,
 	))
 }
 
-/// Configure initial storage state for FRAME modules.
+/// Configure initial storage state for FRAME modules, endowing every account with `ENDOWMENT`.
 fn testnet_genesis(
 	wasm_binary: &[u8],
-	initial_authorities: Vec<(AuraId, GrandpaId)>,
+	initial_authorities: Vec<AuthorityKeys>,
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
+	vesting: Vec<VestingSchedule>,
 	_enable_println: bool,
+) -> GenesisConfig {
+	genesis(
+		wasm_binary,
+		initial_authorities,
+		root_key,
+		endowed_accounts.into_iter().map(|k| (k, ENDOWMENT)).collect(),
+		vesting,
+	)
+}
+
+/// Configure initial storage state for FRAME modules. The stashes of the authorities bond `STASH`
+/// each, so they must be endowed with at least that much. Their controllers are the genesis
+/// members of the council, each standing with a `STASH` stake and paying the voting bond, so they
+/// must be distinct and endowed with at least `STASH` plus the candidacy and voting bonds.
+pub fn genesis(
+	wasm_binary: &[u8],
+	initial_authorities: Vec<AuthorityKeys>,
+	root_key: AccountId,
+	balances: Vec<(AccountId, Balance)>,
+	vesting: Vec<VestingSchedule>,
 ) -> GenesisConfig {
 	GenesisConfig {
 		frame_system: Some(SystemConfig {
@@ -140,18 +415,65 @@ fn testnet_genesis(
 			changes_trie_config: Default::default(),
 		}),
 		pallet_balances: Some(BalancesConfig {
-			// Configure endowed accounts with initial balance of 1 << 60.
-			balances: endowed_accounts.iter().cloned().map(|k|(k, 1 << 60)).collect(),
+			balances,
 		}),
+		// Block authoring and GRANDPA authorities are set by the session pallet from the elected
+		// validators. BABE's epoch length and slot assignment are runtime constants, served to the
+		// node through `BabeApi::configuration`.
+		#[cfg(not(feature = "babe"))]
 		pallet_aura: Some(AuraConfig {
-			authorities: initial_authorities.iter().map(|x| (x.0.clone())).collect(),
+			authorities: vec![],
+		}),
+		#[cfg(feature = "babe")]
+		pallet_babe: Some(BabeConfig {
+			authorities: vec![],
 		}),
 		pallet_grandpa: Some(GrandpaConfig {
-			authorities: initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect(),
+			authorities: vec![],
+		}),
+		pallet_staking: Some(StakingConfig {
+			validator_count: initial_authorities.len() as u32 * 2,
+			minimum_validator_count: initial_authorities.len() as u32,
+			stakers: initial_authorities.iter().map(|x| {
+				(x.0.clone(), x.1.clone(), STASH, StakerStatus::Validator)
+			}).collect(),
+			invulnerables: initial_authorities.iter().map(|x| x.0.clone()).collect(),
+			slash_reward_fraction: Perbill::from_percent(10),
+			.. Default::default()
+		}),
+		pallet_session: Some(SessionConfig {
+			keys: initial_authorities.iter().map(|x| {
+				(x.0.clone(), x.0.clone(), session_keys(x.2.clone(), x.3.clone()))
+			}).collect(),
+		}),
+		pallet_democracy: Some(DemocracyConfig::default()),
+		// The council is seated by the elections pallet.
+		pallet_collective_Instance1: Some(CouncilConfig::default()),
+		pallet_elections_phragmen: Some(ElectionsConfig {
+			members: initial_authorities.iter().map(|x| (x.1.clone(), STASH)).collect(),
+		}),
+		// The technical committee is seated by the membership pallet.
+		pallet_collective_Instance2: Some(TechnicalCommitteeConfig::default()),
+		pallet_membership_Instance1: Some(TechnicalMembershipConfig {
+			members: initial_authorities.iter().map(|x| x.1.clone()).collect(),
+			phantom: Default::default(),
+		}),
+		// The root key owns and holds the whole supply of the genesis assets.
+		pallet_fungible_assets: Some(AssetsConfig {
+			assets: vec![(LTK, root_key.clone(), b"LTKToken".to_vec(), b"LTK".to_vec(), 18)],
+			balances: vec![(LTK, root_key.clone(), LTK_SUPPLY)],
 		}),
 		pallet_sudo: Some(SudoConfig {
 			// Assign network admin rights.
 			key: root_key,
 		}),
+		pallet_treasury: Some(Default::default()),
+		pallet_indices: Some(IndicesConfig {
+			indices: vec![],
+		}),
+		// Vesting accounts must be endowed; whatever exceeds the liquid amount is locked.
+		pallet_vesting: Some(VestingConfig {
+			vesting,
+		}),
 	}
 }
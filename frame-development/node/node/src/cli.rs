@@ -1,3 +1,4 @@
+use std::str::FromStr;
 use structopt::StructOpt;
 use sc_cli::RunCmd;
 
@@ -8,6 +9,42 @@ pub struct Cli {
 
 	#[structopt(flatten)]
 	pub run: RunCmd,
+
+	/// Author blocks without Aura and GRANDPA, for development: `instant` seals a block as soon
+	/// as a transaction enters the pool, `manual` only when asked to by the `engine_createBlock`
+	/// RPC, and a number of milliseconds seals blocks at that interval.
+	#[structopt(long, value_name = "instant|manual|MILLISECONDS")]
+	pub sealing: Option<Sealing>,
+}
+
+/// When a node running without consensus seals blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sealing {
+	/// As soon as a transaction enters the pool.
+	Instant,
+	/// Only on `engine_createBlock` RPC calls.
+	Manual,
+	/// Every given number of milliseconds, whether or not there are transactions.
+	Interval(u64),
+}
+
+impl FromStr for Sealing {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"instant" => Ok(Sealing::Instant),
+			"manual" => Ok(Sealing::Manual),
+			millis => match millis.parse() {
+				Ok(0) | Err(_) => Err(format!(
+					"Invalid sealing `{}`: expected `instant`, `manual` or a positive number of \
+					milliseconds",
+					s,
+				)),
+				Ok(millis) => Ok(Sealing::Interval(millis)),
+			},
+		}
+	}
 }
 
 #[derive(Debug, StructOpt)]
@@ -33,6 +70,31 @@ pub enum Subcommand {
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
 
+	/// Generate the plain and raw chain specs of a network from a TOML or YAML file.
+	GenerateSpec(crate::generate_spec::GenerateSpecCmd),
+
+	/// Build a development chain spec from the state of a live chain exported by `export-state`.
+	ForkSpec(crate::fork_spec::ForkSpecCmd),
+
+	/// Generate, inspect and insert keys.
+	Key(crate::key::KeySubcommand),
+
+	/// List the chains `--chain` accepts by ID.
+	ListChains,
+
+	/// Upgrade the runtime of a running chain to a Wasm file, with a sudo call.
+	UpgradeRuntime(crate::upgrade::UpgradeRuntimeCmd),
+
+	/// Dry-run runtime upgrades against a snapshot of the state of a chain.
+	TryRuntime(crate::try_runtime::TryRuntimeCmd),
+
+	/// Decode blocks and extrinsics, with their events.
+	Inspect(crate::inspect::InspectCmd),
+
+	/// Export the metadata of the runtime without a running node, or compare the metadata of two
+	/// runtimes.
+	ExportMetadata(crate::metadata::ExportMetadataCmd),
+
 	/// The custom benchmark subcommmand benchmarking runtime pallets.
 	#[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
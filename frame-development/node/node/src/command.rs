@@ -15,11 +15,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{chain_spec, service};
+use crate::{registry, sealing, service};
 use crate::cli::{Cli, Subcommand};
+use crate::key::KeySubcommand;
 use sc_cli::{SubstrateCli, RuntimeVersion, Role, ChainSpec};
 use sc_service::PartialComponents;
-use node_template_runtime::Block;
+use node_template_runtime::{Block, SS58Prefix};
+use sp_core::crypto::{set_default_ss58_version, Ss58AddressFormat};
 
 impl SubstrateCli for Cli {
 	fn impl_name() -> String {
@@ -47,13 +49,7 @@ impl SubstrateCli for Cli {
 	}
 
 	fn load_spec(&self, id: &str) -> Result<Box<dyn sc_service::ChainSpec>, String> {
-		Ok(match id {
-			"dev" => Box::new(chain_spec::development_config()?),
-			"" | "local" => Box::new(chain_spec::local_testnet_config()?),
-			path => Box::new(chain_spec::ChainSpec::from_json_file(
-				std::path::PathBuf::from(path),
-			)?),
-		})
+		registry::load(id)
 	}
 
 	fn native_runtime_version(_: &Box<dyn ChainSpec>) -> &'static RuntimeVersion {
@@ -64,6 +60,9 @@ impl SubstrateCli for Cli {
 /// Parse and run command line arguments
 pub fn run() -> sc_cli::Result<()> {
 	let cli = Cli::from_args();
+	// Print and parse the addresses of the chain, in logs and key subcommands alike, with its
+	// own prefix.
+	set_default_ss58_version(Ss58AddressFormat::Custom(SS58Prefix::get()));
 
 	match &cli.subcommand {
 		Some(Subcommand::BuildSpec(cmd)) => {
@@ -114,6 +113,27 @@ pub fn run() -> sc_cli::Result<()> {
 				Ok((cmd.run(client, backend), task_manager))
 			})
 		},
+		Some(Subcommand::GenerateSpec(cmd)) => cmd.run(),
+		Some(Subcommand::ForkSpec(cmd)) => cmd.run(),
+		Some(Subcommand::Key(KeySubcommand::Insert(cmd))) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| cmd.run(config.keystore))
+		},
+		Some(Subcommand::Key(cmd)) => cmd.run(),
+		Some(Subcommand::ListChains) => {
+			registry::list_chains();
+			Ok(())
+		},
+		Some(Subcommand::UpgradeRuntime(cmd)) => cmd.run(),
+		Some(Subcommand::TryRuntime(cmd)) => cmd.run(),
+		Some(Subcommand::Inspect(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| {
+				let PartialComponents { client, .. } = service::new_partial(&config)?;
+				cmd.run(&client)
+			})
+		},
+		Some(Subcommand::ExportMetadata(cmd)) => cmd.run(),
 		Some(Subcommand::Benchmark(cmd)) => {
 			if cfg!(feature = "runtime-benchmarks") {
 				let runner = cli.create_runner(cmd)?;
@@ -126,10 +146,19 @@ pub fn run() -> sc_cli::Result<()> {
 		},
 		None => {
 			let runner = cli.create_runner(&cli.run)?;
-			runner.run_node_until_exit(|config| match config.role {
-				Role::Light => service::new_light(config),
-				_ => service::new_full(config),
-			})
+			registry::validate(&*runner.config().chain_spec)?;
+			match cli.sealing {
+				Some(_) if cfg!(feature = "babe") => Err("Sealing without consensus is only \
+				supported by the Aura build, as BABE needs a pre-digest in every block.".into()),
+				Some(sealing) => runner.run_node_until_exit(|config| match config.role {
+					Role::Light => Err("Light clients cannot seal blocks.".into()),
+					_ => sealing::new_full(config, sealing),
+				}),
+				None => runner.run_node_until_exit(|config| match config.role {
+					Role::Light => service::new_light(config),
+					_ => service::new_full(config),
+				}),
+			}
 		}
 	}
 }
@@ -0,0 +1,267 @@
+//! The `fork-spec` subcommand, turning the state of a live chain exported by `export-state` into
+//! the spec of a development chain that starts from that state, with the local runtime and the
+//! development keys.
+
+use std::path::PathBuf;
+use codec::{Decode, Encode};
+use structopt::StructOpt;
+use node_template_runtime::{Balance, Index, WASM_BINARY};
+use frame_support::traits::LockIdentifier;
+use pallet_balances::{AccountData, BalanceLock, Reasons};
+use sc_service::{ChainSpec as _, ChainType};
+use sp_core::{hexdisplay::HexDisplay, storage::Storage, twox_128};
+use sp_runtime::BuildStorage;
+use crate::chain_spec::{self, ChainSpec};
+use crate::storage::storage_prefix;
+
+/// The pallets whose live state is replaced by that of the development genesis: block authoring,
+/// finality, the validator set and sudo. The live validators' keys are unknown, so the fork would
+/// not produce or finalize a block with their state.
+const DEV_PALLETS: &[&[u8]] = &[
+	b"System",
+	b"Timestamp",
+	b"Aura",
+	b"Babe",
+	b"GrandpaFinality",
+	b"Authorship",
+	b"Staking",
+	b"Session",
+	b"Offences",
+	b"Sudo",
+	b"SudoHandover",
+];
+
+/// The ID of the lock `pallet_staking` puts on bonded funds. The live staking ledgers are replaced
+/// by those of the development genesis, so the live staking locks are dropped with them.
+const STAKING_LOCK: LockIdentifier = *b"staking ";
+
+type AccountInfo = frame_system::AccountInfo<Index, AccountData<Balance>>;
+
+/// Build a development chain spec from the state of a live chain.
+#[derive(Debug, StructOpt)]
+pub struct ForkSpecCmd {
+	/// The state of the live chain, as written by `export-state`.
+	#[structopt(long, parse(from_os_str))]
+	pub state: PathBuf,
+
+	/// The file to write the raw chain spec of the fork to.
+	#[structopt(long, parse(from_os_str), default_value = "fork.json")]
+	pub output: PathBuf,
+}
+
+/// Copy the live state over the development genesis, except for the `DEV_PALLETS` and the
+/// well-known keys such as `:code`, but keeping the live accounts without their staking locks.
+/// The accounts of the development genesis keep their genesis balances and locks, so that the
+/// development validator stays bonded even if a live account has the same ID.
+fn merge(genesis: &mut Storage, live: Storage) -> Result<(), String> {
+	let accounts = storage_prefix(b"System", b"Account");
+	let locks = storage_prefix(b"Balances", b"Locks");
+	let dev_prefixes: Vec<_> = DEV_PALLETS.iter().map(|pallet| twox_128(pallet)).collect();
+	// Both maps are keyed by account with the same hasher, so only their prefixes differ.
+	let account_key = |key: &[u8], prefix: &[u8]| [&accounts[..], &key[prefix.len()..]].concat();
+
+	let mut unlocked = Vec::new();
+	for (key, mut value) in live.top {
+		if key.starts_with(&accounts) || key.starts_with(&locks) {
+			let prefix = if key.starts_with(&accounts) { &accounts } else { &locks };
+			if genesis.top.contains_key(&account_key(&key, prefix)) {
+				continue;
+			}
+			if prefix == &locks {
+				let mut account_locks = Vec::<BalanceLock<Balance>>::decode(&mut &value[..])
+					.map_err(|e| format!("Invalid locks 0x{}: {}", HexDisplay::from(&key), e))?;
+				let count = account_locks.len();
+				account_locks.retain(|lock| lock.id != STAKING_LOCK);
+				if account_locks.len() != count {
+					unlocked.push(account_key(&key, prefix));
+				}
+				if account_locks.is_empty() {
+					continue;
+				}
+				value = account_locks.encode();
+			}
+			genesis.top.insert(key, value);
+		} else {
+			let well_known = key.starts_with(b":");
+			let dev = dev_prefixes.iter().any(|prefix| key.starts_with(prefix));
+			if !(well_known || dev) {
+				genesis.top.insert(key, value);
+			}
+		}
+	}
+	genesis.children_default.extend(live.children_default);
+
+	// The frozen balances of an account are the largest of its locks, for each reason.
+	for key in unlocked {
+		let remaining = match genesis.top.get(&[&locks[..], &key[accounts.len()..]].concat()) {
+			Some(value) => Vec::<BalanceLock<Balance>>::decode(&mut &value[..])
+				.expect("locks are re-encoded by `merge`; qed"),
+			None => vec![],
+		};
+		let frozen = |reasons: Reasons| remaining.iter()
+			.filter(|lock| lock.reasons == reasons || lock.reasons == Reasons::All)
+			.map(|lock| lock.amount)
+			.max()
+			.unwrap_or(0);
+		if let Some(value) = genesis.top.get_mut(&key) {
+			let mut account = AccountInfo::decode(&mut &value[..])
+				.map_err(|e| format!("Invalid account 0x{}: {}", HexDisplay::from(&key), e))?;
+			account.data.misc_frozen = frozen(Reasons::Misc);
+			account.data.fee_frozen = frozen(Reasons::Fee);
+			*value = account.encode();
+		}
+	}
+
+	// The development accounts come on top of the live ones, so the issuance is counted again.
+	let mut total_issuance: Balance = 0;
+	for (key, value) in genesis.top.iter().filter(|(key, _)| key.starts_with(&accounts)) {
+		let account = AccountInfo::decode(&mut &value[..])
+			.map_err(|e| format!("Invalid account 0x{}: {}", HexDisplay::from(key), e))?;
+		total_issuance = total_issuance.saturating_add(account.data.free)
+			.saturating_add(account.data.reserved);
+	}
+	genesis.top.insert(storage_prefix(b"Balances", b"TotalIssuance"), total_issuance.encode());
+
+	Ok(())
+}
+
+impl ForkSpecCmd {
+	/// Run the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let wasm_binary = WASM_BINARY.ok_or("Development wasm binary not available".to_string())?;
+
+		let live = ChainSpec::from_json_file(self.state.clone())
+			.map_err(|e| format!("Invalid state {}: {}", self.state.display(), e))?;
+		let live_storage = live.build_storage()?;
+
+		// No boot nodes nor protocol ID, so that the fork never connects to the live network.
+		let mut fork = ChainSpec::from_genesis(
+			&format!("{} Fork", live.name()),
+			&format!("{}_fork", live.id()),
+			ChainType::Development,
+			move || chain_spec::development_genesis(wasm_binary),
+			vec![],
+			None,
+			None,
+			Some(live.properties()),
+			None,
+		);
+		let mut storage = fork.build_storage()?;
+		merge(&mut storage, live_storage)?;
+		fork.set_storage(storage);
+
+		std::fs::write(&self.output, fork.as_json(true)?)?;
+		println!(
+			"Wrote {}, start it with `--chain {} --alice --tmp`",
+			self.output.display(),
+			self.output.display(),
+		);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use node_template_runtime::AccountId;
+	use sp_core::blake2_128;
+
+	fn account_key(prefix: &[u8], who: u8) -> Vec<u8> {
+		let who = AccountId::new([who; 32]).encode();
+		[prefix, &blake2_128(&who)[..], &who[..]].concat()
+	}
+
+	fn account(free: Balance, reserved: Balance, frozen: Balance) -> Vec<u8> {
+		AccountInfo {
+			data: AccountData { free, reserved, misc_frozen: frozen, fee_frozen: frozen },
+			..Default::default()
+		}.encode()
+	}
+
+	fn lock(id: LockIdentifier, amount: Balance, reasons: Reasons) -> BalanceLock<Balance> {
+		BalanceLock { id, amount, reasons }
+	}
+
+	fn storage(top: Vec<(Vec<u8>, Vec<u8>)>) -> Storage {
+		Storage { top: top.into_iter().collect(), children_default: Default::default() }
+	}
+
+	#[test]
+	fn dev_pallets_and_well_known_keys_are_not_copied() {
+		let sudo_key = storage_prefix(b"Sudo", b"Key");
+		let number = storage_prefix(b"System", b"Number");
+		let something = storage_prefix(b"TemplateModule", b"Something");
+		let mut genesis = storage(vec![
+			(b":code".to_vec(), b"dev".to_vec()),
+			(sudo_key.clone(), b"dev".to_vec()),
+		]);
+		let live = storage(vec![
+			(b":code".to_vec(), b"live".to_vec()),
+			(b":heappages".to_vec(), b"live".to_vec()),
+			(sudo_key.clone(), b"live".to_vec()),
+			(number.clone(), b"live".to_vec()),
+			(something.clone(), b"live".to_vec()),
+		]);
+
+		merge(&mut genesis, live).unwrap();
+		assert_eq!(genesis.top.get(&b":code"[..]), Some(&b"dev".to_vec()));
+		assert_eq!(genesis.top.get(&b":heappages"[..]), None);
+		assert_eq!(genesis.top.get(&sudo_key), Some(&b"dev".to_vec()));
+		assert_eq!(genesis.top.get(&number), None);
+		assert_eq!(genesis.top.get(&something), Some(&b"live".to_vec()));
+	}
+
+	#[test]
+	fn total_issuance_counts_the_live_and_dev_accounts() {
+		let accounts = storage_prefix(b"System", b"Account");
+		let mut genesis = storage(vec![(account_key(&accounts, 1), account(100, 0, 0))]);
+		let live = storage(vec![
+			// A live account with the ID of a development account does not replace it.
+			(account_key(&accounts, 1), account(7, 0, 0)),
+			(account_key(&accounts, 2), account(50, 5, 0)),
+			(storage_prefix(b"Balances", b"TotalIssuance"), 57u128.encode()),
+		]);
+
+		merge(&mut genesis, live).unwrap();
+		assert_eq!(genesis.top.get(&account_key(&accounts, 1)), Some(&account(100, 0, 0)));
+		assert_eq!(genesis.top.get(&account_key(&accounts, 2)), Some(&account(50, 5, 0)));
+		assert_eq!(
+			genesis.top.get(&storage_prefix(b"Balances", b"TotalIssuance")),
+			Some(&155u128.encode()),
+		);
+	}
+
+	#[test]
+	fn live_staking_locks_are_dropped() {
+		let accounts = storage_prefix(b"System", b"Account");
+		let locks = storage_prefix(b"Balances", b"Locks");
+		let dev_lock = vec![lock(STAKING_LOCK, 30, Reasons::All)].encode();
+		let mut genesis = storage(vec![
+			(account_key(&accounts, 1), account(100, 0, 30)),
+			(account_key(&locks, 1), dev_lock.clone()),
+		]);
+		let live = storage(vec![
+			(account_key(&accounts, 2), account(100, 0, 40)),
+			(account_key(&locks, 2), vec![
+				lock(STAKING_LOCK, 40, Reasons::All),
+				lock(*b"vesting ", 10, Reasons::Misc),
+			].encode()),
+			(account_key(&accounts, 3), account(100, 0, 40)),
+			(account_key(&locks, 3), vec![lock(STAKING_LOCK, 40, Reasons::All)].encode()),
+		]);
+
+		merge(&mut genesis, live).unwrap();
+		// The development validator stays bonded.
+		assert_eq!(genesis.top.get(&account_key(&locks, 1)), Some(&dev_lock));
+		assert_eq!(
+			genesis.top.get(&account_key(&locks, 2)),
+			Some(&vec![lock(*b"vesting ", 10, Reasons::Misc)].encode()),
+		);
+		let account_2 = AccountInfo::decode(&mut &genesis.top[&account_key(&accounts, 2)][..]);
+		let data = account_2.unwrap().data;
+		assert_eq!((data.misc_frozen, data.fee_frozen), (10, 0));
+
+		assert_eq!(genesis.top.get(&account_key(&locks, 3)), None);
+		assert_eq!(genesis.top.get(&account_key(&accounts, 3)), Some(&account(100, 0, 0)));
+	}
+}
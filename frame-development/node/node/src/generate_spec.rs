@@ -0,0 +1,213 @@
+//! The `generate-spec` subcommand, building the plain and raw chain specs of a network from a
+//! TOML or YAML description instead of a preset in `chain_spec.rs`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::TryFrom;
+use std::path::PathBuf;
+use serde::Deserialize;
+use structopt::StructOpt;
+use node_template_runtime::{Balance, CandidacyBond, VotingBond, WASM_BINARY};
+use sc_service::{config::MultiaddrWithPeerId, ChainType, Properties};
+use sp_finality_grandpa::AuthorityId as GrandpaId;
+use crate::chain_spec::{
+	self, parse_account, parse_public, AuthorityKeys, ChainSpec, ConsensusId, STASH,
+};
+
+/// Generate the plain and raw chain specs of a network from a TOML or YAML file.
+#[derive(Debug, StructOpt)]
+pub struct GenerateSpecCmd {
+	/// The description of the network, a `.toml`, `.yaml` or `.yml` file.
+	#[structopt(long, parse(from_os_str))]
+	pub config: PathBuf,
+
+	/// The directory to write `<id>.json` and `<id>-raw.json` to.
+	#[structopt(long, parse(from_os_str), default_value = ".")]
+	pub output: PathBuf,
+}
+
+/// The description of a network.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NetworkConfig {
+	/// The human readable name of the chain.
+	name: String,
+	/// The ID of the chain, also naming its database directory.
+	id: String,
+	/// `Development`, `Local` or `Live`, the default.
+	#[serde(default = "live")]
+	chain_type: ChainType,
+	/// The sudo account.
+	sudo: String,
+	/// The genesis authorities.
+	authorities: Vec<Authority>,
+	/// The endowed accounts.
+	#[serde(default)]
+	balances: Vec<Endowment>,
+	/// The addresses of the boot nodes, ending with their peer IDs.
+	#[serde(default)]
+	boot_nodes: Vec<String>,
+	/// The protocol ID, keeping the network apart from other chains.
+	protocol_id: Option<String>,
+	/// The chain properties, overriding the `tokenSymbol`, `tokenDecimals` and `ss58Format` of the
+	/// presets.
+	#[serde(default)]
+	properties: Properties,
+}
+
+fn live() -> ChainType {
+	ChainType::Live
+}
+
+/// A genesis authority.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Authority {
+	/// Keys derived from a secret URI, as by `chain_spec::authority_keys_from_suri`.
+	Seed { seed: String },
+	/// Accounts and public keys, as SS58 addresses or in hex.
+	Keys { stash: String, controller: String, authoring: String, grandpa: String },
+}
+
+/// An endowed account.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Endowment {
+	account: String,
+	balance: Amount,
+}
+
+/// A balance, given as a string when it does not fit in a TOML integer.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Amount {
+	Number(u64),
+	Text(String),
+}
+
+impl TryFrom<&Amount> for Balance {
+	type Error = String;
+
+	fn try_from(amount: &Amount) -> Result<Self, Self::Error> {
+		match amount {
+			Amount::Number(n) => Ok(*n as Balance),
+			Amount::Text(s) => s.replace('_', "").parse()
+				.map_err(|_| format!("Invalid balance `{}`", s)),
+		}
+	}
+}
+
+impl Authority {
+	fn keys(&self) -> Result<AuthorityKeys, String> {
+		match self {
+			Authority::Seed { seed } => chain_spec::authority_keys_from_suri(seed),
+			Authority::Keys { stash, controller, authoring, grandpa } => Ok((
+				parse_account(stash)?,
+				parse_account(controller)?,
+				parse_public::<ConsensusId>(authoring)?,
+				parse_public::<GrandpaId>(grandpa)?,
+			)),
+		}
+	}
+}
+
+impl GenerateSpecCmd {
+	/// Read the description of the network.
+	fn read_config(&self) -> Result<NetworkConfig, String> {
+		let content = std::fs::read_to_string(&self.config)
+			.map_err(|e| format!("Cannot read {}: {}", self.config.display(), e))?;
+		let extension = self.config.extension().and_then(|e| e.to_str()).unwrap_or_default();
+		match extension {
+			"toml" => toml::from_str(&content).map_err(|e| e.to_string()),
+			"yaml" | "yml" => serde_yaml::from_str(&content).map_err(|e| e.to_string()),
+			_ => Err(format!("Unknown format {}: expected a .toml, .yaml or .yml file",
+				self.config.display())),
+		}.map_err(|e| format!("Invalid network description: {}", e))
+	}
+
+	/// Build the chain spec of the network, checking that its genesis can be built.
+	fn chain_spec(config: NetworkConfig) -> Result<ChainSpec, String> {
+		let wasm_binary = WASM_BINARY.ok_or("Development wasm binary not available".to_string())?;
+
+		let authorities = config.authorities.iter()
+			.map(Authority::keys)
+			.collect::<Result<Vec<_>, _>>()?;
+		if authorities.is_empty() {
+			return Err("At least one authority is required".into());
+		}
+		let sudo = parse_account(&config.sudo)?;
+
+		let mut balances = BTreeMap::new();
+		for endowment in &config.balances {
+			let who = parse_account(&endowment.account)?;
+			let balance = Balance::try_from(&endowment.balance)?;
+			if balances.insert(who, balance).is_some() {
+				return Err(format!("`{}` is endowed twice", endowment.account));
+			}
+		}
+		// The stashes bond `STASH` at genesis, and the controllers stand for the council with a
+		// `STASH` stake, so the genesis would panic rather than fail on missing funds.
+		let controller_funds = STASH + CandidacyBond::get() + VotingBond::get();
+		let mut controllers = BTreeSet::new();
+		for (stash, controller, ..) in &authorities {
+			if balances.get(stash).map_or(true, |balance| *balance < STASH) {
+				return Err(format!(
+					"The stash {} of an authority must be endowed with at least {}",
+					stash, STASH,
+				));
+			}
+			if balances.get(controller).map_or(true, |balance| *balance < controller_funds) {
+				return Err(format!(
+					"The controller {} of an authority must be endowed with at least {}",
+					controller, controller_funds,
+				));
+			}
+			if !controllers.insert(controller) {
+				return Err(format!("The controller {} is shared by two authorities", controller));
+			}
+		}
+		let balances: Vec<_> = balances.into_iter().collect();
+
+		let mut properties = chain_spec::properties();
+		properties.extend(config.properties);
+
+		let boot_nodes = config.boot_nodes.iter()
+			.map(|node| node.parse::<MultiaddrWithPeerId>()
+				.map_err(|e| format!("Invalid boot node `{}`: {}", node, e)))
+			.collect::<Result<Vec<_>, _>>()?;
+
+		Ok(ChainSpec::from_genesis(
+			&config.name,
+			&config.id,
+			config.chain_type,
+			move || chain_spec::genesis(
+				wasm_binary,
+				authorities.clone(),
+				sudo.clone(),
+				balances.clone(),
+				vec![],
+			),
+			boot_nodes,
+			None,
+			config.protocol_id.as_deref(),
+			Some(properties),
+			None,
+		))
+	}
+
+	/// Run the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let config = self.read_config()?;
+		let id = config.id.clone();
+		let spec = Self::chain_spec(config)?;
+
+		std::fs::create_dir_all(&self.output)?;
+		for (raw, file) in [(false, format!("{}.json", id)), (true, format!("{}-raw.json", id))]
+			.iter()
+		{
+			let path = self.output.join(file);
+			std::fs::write(&path, spec.as_json(*raw)?)?;
+			println!("Wrote {}", path.display());
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,354 @@
+//! The `inspect` subcommand, decoding the blocks of the local database and their extrinsics and
+//! events for humans, as text or JSON.
+
+use std::str::FromStr;
+use codec::{Compact, Decode, Encode};
+use serde::Serialize;
+use structopt::StructOpt;
+use node_template_runtime::{
+	opaque::Block, Balance, BlockNumber, Call, Event, Hash, Index, UncheckedExtrinsic,
+};
+use sc_cli::{CliConfiguration, ImportParams, SharedParams};
+use sc_client_api::{BlockBackend, StorageProvider};
+use frame_support::traits::GetCallMetadata;
+use frame_system::{EventRecord, Phase};
+use pallet_indices::address::Address;
+use sp_core::{bytes::from_hex, crypto::Ss58Codec, storage::StorageKey};
+use sp_runtime::generic::{BlockId, Era};
+use sp_runtime::traits::{BlakeTwo256, Block as BlockT, Hash as HashT, Header as HeaderT};
+use crate::service::Executor;
+use crate::storage::storage_prefix;
+
+type FullClient = sc_service::TFullClient<Block, node_template_runtime::RuntimeApi, Executor>;
+
+/// Decode blocks and extrinsics.
+#[derive(Debug, StructOpt)]
+pub struct InspectCmd {
+	#[structopt(subcommand)]
+	pub command: InspectSubcommand,
+
+	/// The output format, `text` or `json`.
+	#[structopt(long, value_name = "text|json", default_value = "text")]
+	pub format: Format,
+
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[structopt(flatten)]
+	pub import_params: ImportParams,
+}
+
+/// What to decode.
+#[derive(Debug, StructOpt)]
+pub enum InspectSubcommand {
+	/// Decode a block of the local database, with its extrinsics and events.
+	Block {
+		/// The number of the block, or its hash.
+		block: BlockAddress,
+	},
+
+	/// Decode an extrinsic, given in hex or as `<block>:<index>` in the local database. The events
+	/// of an extrinsic are only known in a block.
+	Extrinsic {
+		/// The SCALE encoded extrinsic in hex, or its block, by number or hash, and index.
+		extrinsic: ExtrinsicAddress,
+	},
+}
+
+/// How to print what was decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+	/// Indented text, for humans.
+	Text,
+	/// Pretty-printed JSON, for scripts.
+	Json,
+}
+
+impl FromStr for Format {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"text" => Ok(Format::Text),
+			"json" => Ok(Format::Json),
+			_ => Err(format!("Invalid format `{}`: expected `text` or `json`", s)),
+		}
+	}
+}
+
+/// A block of the local database.
+#[derive(Debug, Clone, Copy)]
+pub enum BlockAddress {
+	/// The block of that number in the best chain.
+	Number(BlockNumber),
+	/// The block of that hash.
+	Hash(Hash),
+}
+
+impl FromStr for BlockAddress {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if s.starts_with("0x") {
+			let bytes = from_hex(s).map_err(|e| format!("Invalid block hash `{}`: {}", s, e))?;
+			if bytes.len() != Hash::len_bytes() {
+				return Err(format!("Invalid block hash `{}`: expected 32 bytes", s));
+			}
+			Ok(BlockAddress::Hash(Hash::from_slice(&bytes)))
+		} else {
+			s.parse().map(BlockAddress::Number)
+				.map_err(|_| format!("Invalid block `{}`: expected a number or a 0x hash", s))
+		}
+	}
+}
+
+impl From<BlockAddress> for BlockId<Block> {
+	fn from(address: BlockAddress) -> Self {
+		match address {
+			BlockAddress::Number(number) => BlockId::Number(number),
+			BlockAddress::Hash(hash) => BlockId::Hash(hash),
+		}
+	}
+}
+
+/// An extrinsic to decode.
+#[derive(Debug, Clone)]
+pub enum ExtrinsicAddress {
+	/// The SCALE encoded extrinsic.
+	Bytes(Vec<u8>),
+	/// The extrinsic of that index in a block.
+	InBlock(BlockAddress, u32),
+}
+
+impl FromStr for ExtrinsicAddress {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.find(':') {
+			Some(colon) => {
+				let index = s[colon + 1..].parse()
+					.map_err(|_| format!("Invalid extrinsic index in `{}`", s))?;
+				Ok(ExtrinsicAddress::InBlock(s[..colon].parse()?, index))
+			},
+			None => from_hex(s).map(ExtrinsicAddress::Bytes)
+				.map_err(|e| format!("Invalid extrinsic `{}`: {}", s, e)),
+		}
+	}
+}
+
+/// A decoded block.
+#[derive(Debug, Serialize)]
+struct BlockInfo {
+	number: BlockNumber,
+	hash: Hash,
+	parent_hash: Hash,
+	extrinsics: Vec<ExtrinsicInfo>,
+	/// The events of the initialization and finalization of the block.
+	events: Vec<String>,
+}
+
+/// A decoded extrinsic.
+#[derive(Debug, Serialize)]
+struct ExtrinsicInfo {
+	/// The index of the extrinsic in its block, if known.
+	index: Option<u32>,
+	hash: Hash,
+	/// The signer and signed extensions, for signed extrinsics.
+	signed: Option<SignedInfo>,
+	pallet: String,
+	call: String,
+	/// The arguments of the call, as printed by `Debug`.
+	arguments: String,
+	/// The events the extrinsic deposited, if known.
+	events: Vec<String>,
+}
+
+/// The signer and signed extensions of an extrinsic.
+#[derive(Debug, Serialize)]
+struct SignedInfo {
+	signer: String,
+	nonce: Index,
+	era: String,
+	tip: Balance,
+}
+
+/// The arguments of `call` from its `Debug` output, `Pallet(function(arguments))`.
+fn call_arguments(call: &Call, pallet: &str, function: &str) -> String {
+	let debug = format!("{:?}", call);
+	debug.strip_prefix(pallet)
+		.and_then(|s| s.strip_prefix('('))
+		.and_then(|s| s.strip_suffix(')'))
+		.and_then(|s| s.strip_prefix(function))
+		.map(str::to_string)
+		.unwrap_or(debug)
+}
+
+/// A description of `era`, from the block `number` the extrinsic is in if known.
+fn describe_era(era: Era, number: Option<BlockNumber>) -> String {
+	match (era, number) {
+		(Era::Immortal, _) => "immortal".into(),
+		(Era::Mortal(period, _), Some(number)) => format!(
+			"mortal for {} blocks from block {}",
+			period, era.birth(number as u64),
+		),
+		(Era::Mortal(period, phase), None) => {
+			format!("mortal for {} blocks, phase {}", period, phase)
+		},
+	}
+}
+
+/// Decode the extrinsic `encoded`, at position `index` of the block `number` if known.
+fn decode_extrinsic(
+	encoded: &[u8],
+	index: Option<u32>,
+	number: Option<BlockNumber>,
+	events: &[EventRecord<Event, Hash>],
+) -> Result<ExtrinsicInfo, String> {
+	let extrinsic = UncheckedExtrinsic::decode(&mut &encoded[..])
+		.map_err(|e| format!("Cannot decode extrinsic: {}", e))?;
+
+	let signed = match &extrinsic.signature {
+		Some((address, _, extra)) => {
+			// Only the era, nonce and tip of the signed extensions are encoded, in that order.
+			let (era, nonce, tip) =
+				<(Era, Compact<Index>, Compact<Balance>)>::decode(&mut &extra.encode()[..])
+					.map_err(|e| format!("Cannot decode signed extensions: {}", e))?;
+			let signer = match address {
+				Address::Id(who) => who.to_ss58check(),
+				Address::Index(index) => format!("index {}", index),
+			};
+			Some(SignedInfo { signer, nonce: nonce.0, era: describe_era(era, number), tip: tip.0 })
+		},
+		None => None,
+	};
+
+	let metadata = extrinsic.function.get_call_metadata();
+	let arguments =
+		call_arguments(&extrinsic.function, metadata.pallet_name, metadata.function_name);
+	let events = match index {
+		Some(index) => events.iter()
+			.filter(|record| record.phase == Phase::ApplyExtrinsic(index))
+			.map(|record| format!("{:?}", record.event))
+			.collect(),
+		None => vec![],
+	};
+
+	Ok(ExtrinsicInfo {
+		index,
+		hash: BlakeTwo256::hash(encoded),
+		signed,
+		pallet: metadata.pallet_name.into(),
+		call: metadata.function_name.into(),
+		arguments,
+		events,
+	})
+}
+
+/// Print `extrinsic` as indented text.
+fn print_extrinsic(extrinsic: &ExtrinsicInfo, indent: &str) {
+	let index = extrinsic.index.map_or_else(String::new, |index| format!(" {}", index));
+	println!("{}Extrinsic{} {:?}", indent, index, extrinsic.hash);
+	println!("{}  call: {}.{}{}", indent, extrinsic.pallet, extrinsic.call, extrinsic.arguments);
+	match &extrinsic.signed {
+		Some(signed) => println!(
+			"{}  signed by {}, nonce {}, {}, tip {}",
+			indent, signed.signer, signed.nonce, signed.era, signed.tip,
+		),
+		None => println!("{}  unsigned", indent),
+	}
+	for event in &extrinsic.events {
+		println!("{}  event: {}", indent, event);
+	}
+}
+
+impl InspectCmd {
+	/// Decode the block `address` of the local database.
+	fn block(client: &FullClient, address: BlockAddress) -> Result<BlockInfo, String> {
+		let block = client.block(&address.into())
+			.map_err(|e| format!("Cannot read the block: {}", e))?
+			.ok_or_else(|| format!("No block {:?} in the local database", address))?
+			.block;
+		let header = block.header();
+		let number = *header.number();
+		let hash = header.hash();
+
+		let events_key = StorageKey(storage_prefix(b"System", b"Events"));
+		let events = client.storage(&BlockId::Hash(hash), &events_key)
+			.map_err(|e| format!("Cannot read the events of the block: {}", e))?
+			.map(|data| Vec::<EventRecord<Event, Hash>>::decode(&mut &data.0[..]))
+			.transpose()
+			.map_err(|e| format!("Cannot decode the events of the block: {}", e))?
+			.unwrap_or_default();
+
+		let extrinsics = block.extrinsics().iter().enumerate()
+			.map(|(index, extrinsic)| decode_extrinsic(
+				&extrinsic.encode(), Some(index as u32), Some(number), &events,
+			))
+			.collect::<Result<Vec<_>, _>>()?;
+
+		Ok(BlockInfo {
+			number,
+			hash,
+			parent_hash: *header.parent_hash(),
+			extrinsics,
+			events: events.iter()
+				.filter(|record| !matches!(record.phase, Phase::ApplyExtrinsic(_)))
+				.map(|record| format!("{:?}", record.event))
+				.collect(),
+		})
+	}
+
+	/// Print `value` as JSON.
+	fn print_json<T: Serialize>(value: &T) -> sc_cli::Result<()> {
+		let json = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+		println!("{}", json);
+		Ok(())
+	}
+
+	/// Run the command against the client of the local database.
+	pub fn run(&self, client: &FullClient) -> sc_cli::Result<()> {
+		match &self.command {
+			InspectSubcommand::Block { block } => {
+				let block = Self::block(client, *block)?;
+				if self.format == Format::Json {
+					return Self::print_json(&block);
+				}
+				println!(
+					"Block #{} {:?}, parent {:?}",
+					block.number, block.hash, block.parent_hash,
+				);
+				for extrinsic in &block.extrinsics {
+					print_extrinsic(extrinsic, "  ");
+				}
+				for event in &block.events {
+					println!("  event: {}", event);
+				}
+			},
+			InspectSubcommand::Extrinsic { extrinsic } => {
+				let extrinsic = match extrinsic {
+					ExtrinsicAddress::Bytes(bytes) => decode_extrinsic(bytes, None, None, &[])?,
+					ExtrinsicAddress::InBlock(block, index) => Self::block(client, *block)?
+						.extrinsics
+						.into_iter()
+						.nth(*index as usize)
+						.ok_or_else(|| format!("No extrinsic {} in block {:?}", index, block))?,
+				};
+				if self.format == Format::Json {
+					return Self::print_json(&extrinsic);
+				}
+				print_extrinsic(&extrinsic, "");
+			},
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for InspectCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn import_params(&self) -> Option<&ImportParams> {
+		Some(&self.import_params)
+	}
+}
@@ -0,0 +1,148 @@
+//! Key management subcommands, so that validators do not need a separate `subkey` binary.
+
+use std::convert::TryFrom;
+use std::path::Path;
+use structopt::StructOpt;
+use sc_cli::{CliConfiguration, CryptoScheme, Error, KeystoreParams, SharedParams};
+use sc_service::config::KeystoreConfig;
+use sp_core::{crypto::KeyTypeId, ecdsa, ed25519, hexdisplay::HexDisplay, sr25519, Pair};
+
+/// Generate, inspect and insert keys.
+#[derive(Debug, StructOpt)]
+pub enum KeySubcommand {
+	/// Generate a random node libp2p key, save it to file and print its peer ID.
+	GenerateNodeKey(sc_cli::GenerateNodeKeyCmd),
+
+	/// Generate a random account, or session key with `--scheme`.
+	Generate(sc_cli::GenerateCmd),
+
+	/// Print the public key and account ID of a secret URI or public key.
+	Inspect(sc_cli::InspectKeyCmd),
+
+	/// Print the peer ID of a node libp2p key file.
+	InspectNodeKey(sc_cli::InspectNodeKeyCmd),
+
+	/// Insert a session key into the keystore of the node.
+	Insert(InsertKeyCmd),
+}
+
+impl KeySubcommand {
+	/// Run the subcommand, except `insert` which needs the node configuration to find the
+	/// keystore, see `InsertKeyCmd::run`.
+	pub fn run(&self) -> Result<(), Error> {
+		match self {
+			KeySubcommand::GenerateNodeKey(cmd) => cmd.run(),
+			KeySubcommand::Generate(cmd) => cmd.run(),
+			KeySubcommand::Inspect(cmd) => cmd.run(),
+			KeySubcommand::InspectNodeKey(cmd) => cmd.run(),
+			KeySubcommand::Insert(_) => Err("`key insert` needs the node configuration".into()),
+		}
+	}
+}
+
+/// Insert a session key, derived from a secret URI, into the keystore of the node.
+#[derive(Debug, StructOpt)]
+pub struct InsertKeyCmd {
+	/// The secret URI of the key, e.g. a mnemonic phrase or `//Alice`, or the path to a file
+	/// containing it.
+	#[structopt(long)]
+	pub suri: String,
+
+	/// The key type: `aura`, `babe` or `gran` for the block authoring and GRANDPA session keys,
+	/// or any other four-character key type ID.
+	#[structopt(long, parse(try_from_str = parse_key_type))]
+	pub key_type: KeyTypeId,
+
+	/// The crypto scheme of the key. Defaults to the scheme of the session key of that type,
+	/// sr25519 for `aura` and `babe` and ed25519 for `gran`.
+	#[structopt(
+		long,
+		value_name = "SCHEME",
+		possible_values = &CryptoScheme::variants(),
+		case_insensitive = true,
+	)]
+	pub scheme: Option<CryptoScheme>,
+
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[structopt(flatten)]
+	pub keystore_params: KeystoreParams,
+}
+
+/// Parse a key type ID, `grandpa` being accepted for `gran`.
+fn parse_key_type(s: &str) -> Result<KeyTypeId, String> {
+	match s {
+		"grandpa" => Ok(KeyTypeId(*b"gran")),
+		id => KeyTypeId::try_from(id)
+			.map_err(|_| format!("Invalid key type `{}`: expected four characters", id)),
+	}
+}
+
+/// The secret URI `suri`, or the one written in the file of that path.
+pub fn read_suri(suri: &str) -> Result<String, Error> {
+	Ok(if Path::new(suri).is_file() {
+		std::fs::read_to_string(suri)?.trim().to_string()
+	} else {
+		suri.to_string()
+	})
+}
+
+/// The public key of the `P` pair of `suri`.
+fn public_of<P: Pair>(suri: &str) -> Result<Vec<u8>, Error> {
+	let pair = P::from_string(suri, None)
+		.map_err(|e| format!("Invalid secret URI: {:?}", e))?;
+	Ok(pair.public().as_ref().to_vec())
+}
+
+impl InsertKeyCmd {
+	/// The crypto scheme of the inserted key, as in `chain_spec::authority_keys_from_seed`.
+	fn scheme(&self) -> Result<CryptoScheme, Error> {
+		match (self.scheme, &self.key_type.0) {
+			(Some(scheme), _) => Ok(scheme),
+			(None, b"aura") | (None, b"babe") => Ok(CryptoScheme::Sr25519),
+			(None, b"gran") => Ok(CryptoScheme::Ed25519),
+			(None, _) => Err("`--scheme` is required for key types other than aura, babe and gran"
+				.into()),
+		}
+	}
+
+	/// Run the command, writing into the keystore of the node.
+	pub fn run(&self, keystore: KeystoreConfig) -> Result<(), Error> {
+		let (path, password) = match keystore {
+			KeystoreConfig::Path { path, password } => (path, password),
+			KeystoreConfig::InMemory => return Err("The node has no keystore directory".into()),
+		};
+
+		let suri = read_suri(&self.suri)?;
+
+		let public = match self.scheme()? {
+			CryptoScheme::Sr25519 => public_of::<sr25519::Pair>(&suri)?,
+			CryptoScheme::Ed25519 => public_of::<ed25519::Pair>(&suri)?,
+			CryptoScheme::Ecdsa => public_of::<ecdsa::Pair>(&suri)?,
+		};
+
+		let keystore = sc_keystore::Store::open(path.clone(), password)
+			.map_err(|e| format!("Cannot open the keystore: {}", e))?;
+		keystore.write().insert_unknown(self.key_type, &suri, &public)
+			.map_err(|e| format!("Cannot insert the key: {}", e))?;
+
+		println!(
+			"Inserted the `{}` key 0x{} into {}",
+			String::from_utf8_lossy(&self.key_type.0),
+			HexDisplay::from(&public),
+			path.display(),
+		);
+		Ok(())
+	}
+}
+
+impl CliConfiguration for InsertKeyCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn keystore_params(&self) -> Option<&KeystoreParams> {
+		Some(&self.keystore_params)
+	}
+}
@@ -1,3 +1,7 @@
 pub mod chain_spec;
+#[cfg(not(feature = "babe"))]
+pub mod service;
+#[cfg(feature = "babe")]
+#[path = "service_babe.rs"]
 pub mod service;
 pub mod rpc;
@@ -3,10 +3,26 @@
 
 mod chain_spec;
 #[macro_use]
+#[cfg(not(feature = "babe"))]
+mod service;
+#[macro_use]
+#[cfg(feature = "babe")]
+#[path = "service_babe.rs"]
 mod service;
 mod cli;
 mod command;
+mod fork_spec;
+mod generate_spec;
+mod inspect;
+mod key;
+mod metadata;
+mod registry;
 mod rpc;
+mod sealing;
+mod storage;
+mod try_runtime;
+mod upgrade;
+mod wasm;
 
 fn main() -> sc_cli::Result<()> {
 	command::run()
@@ -0,0 +1,218 @@
+//! The `export-metadata` subcommand, reading the metadata of a runtime from its Wasm blob without a
+//! running node, and comparing the metadata of two runtimes.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use codec::Decode;
+use frame_metadata::{DecodeDifferent, RuntimeMetadata, RuntimeMetadataPrefixed};
+use structopt::StructOpt;
+use node_template_runtime::WASM_BINARY;
+use sp_core::bytes::to_hex;
+use sp_state_machine::BasicExternalities;
+use crate::wasm;
+
+/// Export the metadata of a runtime, or compare the metadata of two runtimes.
+#[derive(Debug, StructOpt)]
+pub struct ExportMetadataCmd {
+	/// The Wasm runtime to read the metadata of. Defaults to the runtime of the node.
+	#[structopt(long, parse(from_os_str))]
+	pub wasm: Option<PathBuf>,
+
+	/// The output format: `scale`, the hex-encoded metadata as returned by `state_getMetadata`,
+	/// or `json`.
+	#[structopt(long, value_name = "scale|json", default_value = "scale")]
+	pub format: MetadataFormat,
+
+	/// The file to write the metadata to, instead of the standard output.
+	#[structopt(long, parse(from_os_str))]
+	pub output: Option<PathBuf>,
+
+	/// Compare the metadata of two Wasm runtimes instead, listing the calls, events, storage
+	/// items and constants added, removed or changed from the first to the second.
+	#[structopt(
+		long,
+		value_names = &["OLD", "NEW"],
+		number_of_values = 2,
+		parse(from_os_str),
+		conflicts_with_all = &["wasm", "output"],
+	)]
+	pub diff: Vec<PathBuf>,
+}
+
+/// How to write the metadata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetadataFormat {
+	/// The SCALE encoded metadata, in hex.
+	Scale,
+	/// The decoded metadata, as JSON.
+	Json,
+}
+
+impl FromStr for MetadataFormat {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"scale" => Ok(MetadataFormat::Scale),
+			"json" => Ok(MetadataFormat::Json),
+			_ => Err(format!("Invalid format `{}`: expected `scale` or `json`", s)),
+		}
+	}
+}
+
+/// The SCALE encoded metadata of the runtime `code`, as returned by its `Metadata_metadata`.
+fn encoded_metadata(code: &[u8]) -> Result<Vec<u8>, String> {
+	let result = wasm::call(code, &mut BasicExternalities::default(), "Metadata_metadata", &[])
+		.map_err(|e| format!("Cannot read the metadata of the runtime: {}", e))?;
+	// The result is the `OpaqueMetadata`, wrapping the encoded metadata.
+	Vec::<u8>::decode(&mut &result[..]).map_err(|e| format!("Invalid metadata: {}", e))
+}
+
+/// The decoded metadata of the runtime `code`.
+fn metadata(code: &[u8]) -> Result<RuntimeMetadataPrefixed, String> {
+	RuntimeMetadataPrefixed::decode(&mut &encoded_metadata(code)?[..])
+		.map_err(|e| format!("Invalid metadata: {}", e))
+}
+
+/// The value of a field of metadata read from a runtime, which is always decoded.
+fn decoded<B, O>(field: &DecodeDifferent<B, O>) -> Result<&O, String> where
+	B: 'static,
+	O: 'static,
+{
+	match field {
+		DecodeDifferent::Decoded(value) => Ok(value),
+		DecodeDifferent::Encode(_) => Err("The metadata is not decoded".into()),
+	}
+}
+
+/// The calls, events, storage items and constants of the runtime `code`, each named by its kind,
+/// pallet and name and mapped to its signature.
+fn items(code: &[u8]) -> Result<BTreeMap<String, String>, String> {
+	let metadata = match metadata(code)?.1 {
+		RuntimeMetadata::V12(metadata) => metadata,
+		_ => return Err("Only version 12 of the metadata can be compared".into()),
+	};
+
+	let mut items = BTreeMap::new();
+	for module in decoded(&metadata.modules)? {
+		let pallet = decoded(&module.name)?;
+
+		if let Some(calls) = &module.calls {
+			for call in decoded(calls)? {
+				let arguments = decoded(&call.arguments)?.iter()
+					.map(|argument| Ok(format!(
+						"{}: {}", decoded(&argument.name)?, decoded(&argument.ty)?,
+					)))
+					.collect::<Result<Vec<_>, String>>()?;
+				items.insert(
+					format!("call {}.{}", pallet, decoded(&call.name)?),
+					format!("({})", arguments.join(", ")),
+				);
+			}
+		}
+
+		if let Some(events) = &module.event {
+			for event in decoded(events)? {
+				let arguments = decoded(&event.arguments)?.iter()
+					.map(|argument| decoded(argument).map(String::as_str))
+					.collect::<Result<Vec<_>, _>>()?;
+				items.insert(
+					format!("event {}.{}", pallet, decoded(&event.name)?),
+					format!("({})", arguments.join(", ")),
+				);
+			}
+		}
+
+		if let Some(storage) = &module.storage {
+			for entry in decoded(&decoded(storage)?.entries)? {
+				let ty = serde_json::to_string(&entry.ty).map_err(|e| e.to_string())?;
+				items.insert(
+					format!("storage {}.{}", pallet, decoded(&entry.name)?),
+					format!(": {}", ty),
+				);
+			}
+		}
+
+		for constant in decoded(&module.constants)? {
+			items.insert(
+				format!("constant {}.{}", pallet, decoded(&constant.name)?),
+				format!(
+					": {} = {}",
+					decoded(&constant.ty)?,
+					to_hex(decoded(&constant.value)?, false),
+				),
+			);
+		}
+	}
+	Ok(items)
+}
+
+/// Read a Wasm runtime.
+fn read_wasm(path: &Path) -> Result<Vec<u8>, String> {
+	std::fs::read(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))
+}
+
+impl ExportMetadataCmd {
+	/// Print the calls, events, storage items and constants added (`+`), removed (`-`) and
+	/// changed (`~`) from the runtime `old` to the runtime `new`.
+	fn diff(old: &Path, new: &Path) -> sc_cli::Result<()> {
+		let old_code = read_wasm(old)?;
+		let new_code = read_wasm(new)?;
+		let old_version = wasm::runtime_version(&old_code)?;
+		let new_version = wasm::runtime_version(&new_code)?;
+		println!(
+			"{} {} -> {} {}",
+			old_version.spec_name, old_version.spec_version,
+			new_version.spec_name, new_version.spec_version,
+		);
+
+		let old_items = items(&old_code)?;
+		let new_items = items(&new_code)?;
+		let mut differences = 0;
+		for (name, signature) in &old_items {
+			match new_items.get(name) {
+				None => println!("- {}{}", name, signature),
+				Some(new) if new != signature => println!("~ {}{} -> {}", name, signature, new),
+				Some(_) => continue,
+			}
+			differences += 1;
+		}
+		for (name, signature) in &new_items {
+			if !old_items.contains_key(name) {
+				println!("+ {}{}", name, signature);
+				differences += 1;
+			}
+		}
+
+		if differences == 0 {
+			println!("The metadata is the same");
+		}
+		Ok(())
+	}
+
+	/// Run the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		if let [old, new] = &self.diff[..] {
+			return Self::diff(old, new);
+		}
+
+		let code = match &self.wasm {
+			Some(path) => read_wasm(path)?,
+			None => WASM_BINARY
+				.ok_or("Development wasm binary not available".to_string())?
+				.to_vec(),
+		};
+		let output = match self.format {
+			MetadataFormat::Scale => to_hex(&encoded_metadata(&code)?, false),
+			MetadataFormat::Json => serde_json::to_string_pretty(&metadata(&code)?)
+				.map_err(|e| e.to_string())?,
+		};
+
+		match &self.output {
+			Some(path) => std::fs::write(path, output)?,
+			None => println!("{}", output),
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,156 @@
+//! The chains the node knows by ID: the presets of `chain_spec.rs` and the raw chain specs
+//! embedded in the binary, and the checks a chain spec must pass before the node starts on it.
+
+use std::path::Path;
+use node_template_runtime::{opaque::Block, VERSION};
+use sp_core::{storage::well_known_keys, H256};
+use sp_runtime::traits::{Block as BlockT, Hash as HashT, Header as HeaderT};
+use crate::chain_spec::{self, ChainSpec};
+use crate::wasm;
+
+/// The presets `--chain` accepts, and what they are.
+pub const PRESETS: &[(&str, &str)] = &[
+	("dev", "Development chain validated by Alice, with instant finality"),
+	("local", "Local testnet validated by Alice and Bob"),
+	("staging", "Private network whose keys and boot nodes are read from the environment"),
+];
+
+/// A raw chain spec embedded in the binary.
+pub struct BuiltinSpec {
+	/// The ID `--chain` accepts for it.
+	pub id: &'static str,
+	/// What the chain is.
+	pub description: &'static str,
+	/// The raw JSON chain spec, included from `node/res`.
+	pub json: &'static [u8],
+	/// The hash of its genesis block, in hex.
+	pub genesis_hash: &'static str,
+}
+
+/// The chain specs embedded in the binary. To ship the spec of a network, write its raw chain spec
+/// to `node/res/<id>.json`, e.g. with `build-spec --raw` or `generate-spec`, and list it here:
+///
+/// ```ignore
+/// BuiltinSpec {
+/// 	id: "<id>",
+/// 	description: "<what the chain is>",
+/// 	json: include_bytes!("../res/<id>.json"),
+/// 	genesis_hash: "0x<the header hash the node logs when initializing the genesis block>",
+/// },
+/// ```
+pub const BUILTIN_SPECS: &[BuiltinSpec] = &[];
+
+/// Load the chain spec `--chain` names: a preset, an embedded spec or a spec file.
+pub fn load(id: &str) -> Result<Box<dyn sc_service::ChainSpec>, String> {
+	Ok(match id {
+		"dev" => Box::new(chain_spec::development_config()?),
+		"" | "local" => Box::new(chain_spec::local_testnet_config()?),
+		"staging" => Box::new(chain_spec::staging_config()?),
+		id => match BUILTIN_SPECS.iter().find(|spec| spec.id == id) {
+			Some(spec) => Box::new(ChainSpec::from_json_bytes(spec.json)
+				.map_err(|e| format!("Invalid embedded chain spec `{}`: {}", id, e))?),
+			None if Path::new(id).is_file() => Box::new(
+				ChainSpec::from_json_file(id.into())
+					.map_err(|e| format!("Invalid chain spec file {}: {}", id, e))?,
+			),
+			None => return Err(format!(
+				"Unknown chain `{}`: expected one of {}, or the path to a chain spec file. \
+				Run `list-chains` for details.",
+				id,
+				PRESETS.iter().map(|(id, _)| *id)
+					.chain(BUILTIN_SPECS.iter().map(|spec| spec.id))
+					.collect::<Vec<_>>()
+					.join(", "),
+			)),
+		},
+	})
+}
+
+/// The hash of the genesis block of `spec`.
+pub fn genesis_hash(spec: &dyn sc_service::ChainSpec) -> Result<H256, String> {
+	type Hashing = <<Block as BlockT>::Header as HeaderT>::Hashing;
+
+	let storage = spec.as_storage_builder().build_storage()?;
+	// Child roots are stored in the top trie under the prefixed key of their child trie.
+	let child_roots = storage.children_default.values().map(|child| {
+		let root = Hashing::trie_root(child.data.clone().into_iter().collect());
+		(child.child_info.prefixed_storage_key().into_inner(), root.as_ref().to_vec())
+	});
+	let state_root = Hashing::trie_root(
+		storage.top.clone().into_iter().chain(child_roots).collect(),
+	);
+
+	Ok(sc_service::client::genesis::construct_genesis_block::<Block>(state_root).header().hash())
+}
+
+/// Check that the node can run the chain of `spec` before starting on it: its genesis runtime must
+/// be this node's runtime, though possibly at another version, and an embedded spec must still
+/// have its registered genesis hash.
+pub fn validate(spec: &dyn sc_service::ChainSpec) -> Result<(), String> {
+	let storage = spec.as_storage_builder().build_storage()?;
+	let code = storage.top.get(well_known_keys::CODE)
+		.ok_or_else(|| format!("The chain spec `{}` has no genesis runtime", spec.id()))?;
+
+	let version = wasm::runtime_version(code).map_err(|e| format!(
+		"Cannot read the genesis runtime version of `{}`: {}", spec.id(), e,
+	))?;
+	if version.spec_name != VERSION.spec_name {
+		return Err(format!(
+			"The chain `{}` runs the `{}` runtime, but this node runs `{}`",
+			spec.id(), version.spec_name, VERSION.spec_name,
+		));
+	}
+
+	if let Some(builtin) = BUILTIN_SPECS.iter().find(|builtin| builtin.id == spec.id()) {
+		let hash = format!("{:?}", genesis_hash(spec)?);
+		if hash != builtin.genesis_hash {
+			return Err(format!(
+				"The genesis hash of the chain `{}` is {}, not the {} of the embedded spec",
+				spec.id(), hash, builtin.genesis_hash,
+			));
+		}
+	}
+
+	Ok(())
+}
+
+/// Print the chains `--chain` accepts, and the genesis hashes of the embedded ones.
+pub fn list_chains() {
+	println!("Presets:");
+	for (id, description) in PRESETS {
+		println!("  {:<12} {}", id, description);
+	}
+
+	println!("Embedded chain specs:");
+	if BUILTIN_SPECS.is_empty() {
+		println!("  none");
+	}
+	for spec in BUILTIN_SPECS {
+		println!("  {:<12} {} (genesis {})", spec.id, spec.description, spec.genesis_hash);
+	}
+
+	println!("Any other value of --chain is read as the path to a chain spec file.");
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn presets_are_valid() {
+		// The staging preset reads its keys from the environment.
+		for (id, _) in PRESETS.iter().filter(|(id, _)| *id != "staging") {
+			validate(&*load(id).unwrap()).unwrap();
+		}
+	}
+
+	#[test]
+	fn embedded_specs_load_and_keep_their_genesis_hash() {
+		for builtin in BUILTIN_SPECS {
+			let spec = load(builtin.id).unwrap();
+			assert_eq!(spec.id(), builtin.id);
+			validate(&*spec).unwrap();
+			assert_eq!(format!("{:?}", genesis_hash(&*spec).unwrap()), builtin.genesis_hash);
+		}
+	}
+}
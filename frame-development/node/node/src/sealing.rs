@@ -0,0 +1,204 @@
+//! Service authoring blocks without consensus, for development. Blocks are sealed when a
+//! transaction enters the pool, on RPC calls or at a fixed interval instead of in Aura slots, and
+//! are finalized right away rather than by GRANDPA.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use futures::{channel::mpsc, stream, FutureExt, StreamExt};
+use sc_client_api::StorageProvider;
+use sc_consensus_manual_seal::{rpc::{ManualSeal, ManualSealApi}, EngineCommand, ManualSealParams};
+use node_template_runtime::{self, opaque::Block, Hash, RuntimeApi, SLOT_DURATION};
+use sc_service::{error::Error as ServiceError, Configuration, TaskManager};
+use sp_blockchain::HeaderBackend;
+use sp_core::storage::StorageKey;
+use sp_inherents::{InherentData, InherentDataProviders, InherentIdentifier, ProvideInherentData};
+use sp_runtime::generic::BlockId;
+use crate::cli::Sealing;
+use crate::service::Executor;
+use crate::storage::storage_prefix;
+
+type FullClient = sc_service::TFullClient<Block, RuntimeApi, Executor>;
+
+/// Provides timestamps one slot apart, so that the runtime accepts blocks sealed more often than
+/// once per `SLOT_DURATION`. Time runs ahead of the wall clock while blocks are sealed faster than
+/// that, and never goes back, even when the node restarts.
+struct SlotTimestampProvider {
+	next: AtomicU64,
+}
+
+impl SlotTimestampProvider {
+	/// Start from the slot following the best block, or from the current time if it is later.
+	fn new(client: &FullClient) -> Result<Self, ServiceError> {
+		let key = StorageKey(storage_prefix(b"Timestamp", b"Now"));
+		let best = client.info().best_hash;
+		let best_timestamp = client.storage(&BlockId::Hash(best), &key)?
+			.and_then(|data| {
+				let mut bytes = [0u8; 8];
+				bytes.copy_from_slice(data.0.get(..8)?);
+				Some(u64::from_le_bytes(bytes))
+			})
+			.unwrap_or(0);
+		let now = SystemTime::now().duration_since(UNIX_EPOCH)
+			.map_err(|e| ServiceError::Other(format!("Current time is before 1970: {}", e)))?
+			.as_millis() as u64;
+
+		Ok(SlotTimestampProvider {
+			next: AtomicU64::new(now.max(best_timestamp + SLOT_DURATION)),
+		})
+	}
+}
+
+impl ProvideInherentData for SlotTimestampProvider {
+	fn inherent_identifier(&self) -> &'static InherentIdentifier {
+		&sp_timestamp::INHERENT_IDENTIFIER
+	}
+
+	fn provide_inherent_data(
+		&self,
+		inherent_data: &mut InherentData,
+	) -> Result<(), sp_inherents::Error> {
+		let timestamp: sp_timestamp::InherentType =
+			self.next.fetch_add(SLOT_DURATION, Ordering::SeqCst);
+		inherent_data.put_data(sp_timestamp::INHERENT_IDENTIFIER, &timestamp)
+	}
+
+	fn error_to_string(&self, error: &[u8]) -> Option<String> {
+		sp_timestamp::InherentError::try_from(&sp_timestamp::INHERENT_IDENTIFIER, error)
+			.map(|e| format!("{:?}", e))
+	}
+}
+
+/// The command sealing a new block, finalized right away, for the instant and interval modes.
+fn seal_command(create_empty: bool) -> EngineCommand<Hash> {
+	EngineCommand::SealNewBlock {
+		create_empty,
+		finalize: true,
+		parent_hash: None,
+		sender: None,
+	}
+}
+
+/// Builds a new service for a full client sealing blocks according to `sealing`.
+pub fn new_full(config: Configuration, sealing: Sealing) -> Result<TaskManager, ServiceError> {
+	let (client, backend, keystore, mut task_manager) =
+		sc_service::new_full_parts::<Block, RuntimeApi, Executor>(&config)?;
+	let client = Arc::new(client);
+
+	let select_chain = sc_consensus::LongestChain::new(backend.clone());
+
+	let transaction_pool = sc_transaction_pool::BasicPool::new_full(
+		config.transaction_pool.clone(),
+		config.prometheus_registry(),
+		task_manager.spawn_handle(),
+		client.clone(),
+	);
+
+	let inherent_data_providers = InherentDataProviders::new();
+	inherent_data_providers
+		.register_provider(SlotTimestampProvider::new(&client)?)
+		.map_err(sp_consensus::Error::InherentData)?;
+
+	let import_queue = sc_consensus_manual_seal::import_queue(
+		Box::new(client.clone()),
+		&task_manager.spawn_handle(),
+		config.prometheus_registry(),
+	);
+
+	let (network, network_status_sinks, system_rpc_tx, network_starter) =
+		sc_service::build_network(sc_service::BuildNetworkParams {
+			config: &config,
+			client: client.clone(),
+			transaction_pool: transaction_pool.clone(),
+			spawn_handle: task_manager.spawn_handle(),
+			import_queue,
+			on_demand: None,
+			block_announce_validator_builder: None,
+			finality_proof_request_builder: None,
+			finality_proof_provider: None,
+		})?;
+
+	if config.offchain_worker.enabled {
+		sc_service::build_offchain_workers(
+			&config, backend.clone(), task_manager.spawn_handle(), client.clone(), network.clone(),
+		);
+	}
+
+	let prometheus_registry = config.prometheus_registry().cloned();
+
+	// `engine_createBlock` and `engine_finalizeBlock` calls are available in every mode.
+	let (command_sink, rpc_commands) = mpsc::channel(1024);
+
+	let rpc_extensions_builder = {
+		let client = client.clone();
+		let pool = transaction_pool.clone();
+
+		Box::new(move |deny_unsafe, _| {
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				pool: pool.clone(),
+				deny_unsafe,
+			};
+
+			let mut io = crate::rpc::create_full(deps);
+			io.extend_with(
+				ManualSealApi::to_delegate(ManualSeal::new(command_sink.clone()))
+			);
+
+			io
+		})
+	};
+
+	sc_service::spawn_tasks(sc_service::SpawnTasksParams {
+		network,
+		client: client.clone(),
+		keystore,
+		task_manager: &mut task_manager,
+		transaction_pool: transaction_pool.clone(),
+		telemetry_connection_sinks: sc_service::TelemetryConnectionSinks::default(),
+		rpc_extensions_builder: rpc_extensions_builder,
+		on_demand: None,
+		remote_blockchain: None,
+		backend, network_status_sinks, system_rpc_tx, config,
+	})?;
+
+	let commands_stream = match sealing {
+		Sealing::Instant => {
+			let imported = transaction_pool.pool().validated_pool().import_notification_stream()
+				.map(|_| seal_command(false));
+			stream::select(rpc_commands, imported).boxed()
+		},
+		Sealing::Manual => rpc_commands.boxed(),
+		Sealing::Interval(millis) => {
+			let ticks = stream::unfold((), move |_| {
+				futures_timer::Delay::new(Duration::from_millis(millis))
+					.map(|_| Some((seal_command(true), ())))
+			});
+			stream::select(rpc_commands, ticks).boxed()
+		},
+	};
+
+	let proposer = sc_basic_authorship::ProposerFactory::new(
+		client.clone(),
+		transaction_pool.clone(),
+		prometheus_registry.as_ref(),
+	);
+
+	let authorship_future = sc_consensus_manual_seal::run_manual_seal(ManualSealParams {
+		block_import: client.clone(),
+		env: proposer,
+		client,
+		pool: transaction_pool.pool().clone(),
+		commands_stream,
+		select_chain,
+		consensus_data_provider: None,
+		inherent_data_providers,
+	});
+
+	// the sealing task is considered essential, i.e. if it
+	// fails we take down the service with it.
+	task_manager.spawn_essential_handle().spawn_blocking("manual-seal", authorship_future);
+
+	network_starter.start_network();
+	Ok(task_manager)
+}
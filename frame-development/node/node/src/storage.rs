@@ -0,0 +1,8 @@
+//! Storage keys of the runtime, for the subcommands reading or writing its state from outside.
+
+use sp_core::twox_128;
+
+/// The key prefix of the storage item `item` of `pallet`, which is the whole key of a value.
+pub fn storage_prefix(pallet: &[u8], item: &[u8]) -> Vec<u8> {
+	[twox_128(pallet), twox_128(item)].concat()
+}
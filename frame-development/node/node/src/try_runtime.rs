@@ -0,0 +1,110 @@
+//! The `try-runtime` subcommand, dry-running the upgrade to a runtime against a snapshot of the
+//! state of a chain, so that migrations are tried on real data before `spec_version` is bumped.
+
+use std::path::PathBuf;
+use codec::Decode;
+use structopt::StructOpt;
+use node_template_runtime::{MaximumBlockWeight, Weight, WASM_BINARY};
+use sp_core::storage::well_known_keys;
+use sp_runtime::BuildStorage;
+use sp_state_machine::BasicExternalities;
+use crate::chain_spec::ChainSpec;
+use crate::wasm;
+
+/// Dry-run runtime upgrades.
+#[derive(Debug, StructOpt)]
+pub enum TryRuntimeCmd {
+	/// Run the migrations of the upgrade to a runtime against a snapshot of the state of a chain,
+	/// checking the state of the pallets before and after them.
+	OnRuntimeUpgrade(OnRuntimeUpgradeCmd),
+}
+
+impl TryRuntimeCmd {
+	/// Run the subcommand.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		match self {
+			TryRuntimeCmd::OnRuntimeUpgrade(cmd) => cmd.run(),
+		}
+	}
+}
+
+/// Run the migrations of the upgrade to a runtime against a snapshot of the state of a chain.
+#[derive(Debug, StructOpt)]
+pub struct OnRuntimeUpgradeCmd {
+	/// The state of the chain, as written by `export-state`.
+	#[structopt(long, parse(from_os_str))]
+	pub snapshot: PathBuf,
+
+	/// The Wasm runtime to upgrade to, built with the `try-runtime` feature. Defaults to the
+	/// runtime of the node.
+	#[structopt(long, parse(from_os_str))]
+	pub wasm: Option<PathBuf>,
+}
+
+impl OnRuntimeUpgradeCmd {
+	/// Run the command. The snapshot is loaded into externalities of its own, so neither the
+	/// snapshot nor any database is changed.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let code = match &self.wasm {
+			Some(path) => std::fs::read(path)?,
+			None if cfg!(feature = "try-runtime") => WASM_BINARY
+				.ok_or("Development wasm binary not available".to_string())?
+				.to_vec(),
+			None => return Err("The runtime of the node implements the `TryRuntime` API only when \
+				the node is built with `--features try-runtime`. Build it so, or pass the runtime \
+				to upgrade to with `--wasm`.".into()),
+		};
+
+		let snapshot = ChainSpec::from_json_file(self.snapshot.clone())
+			.map_err(|e| format!("Invalid snapshot {}: {}", self.snapshot.display(), e))?;
+		let mut storage = snapshot.build_storage()?;
+
+		let current_code = storage.top.get(well_known_keys::CODE)
+			.ok_or("The snapshot has no runtime")?;
+		let current = wasm::runtime_version(current_code)
+			.map_err(|e| format!("Invalid runtime in the snapshot: {}", e))?;
+		let new = wasm::runtime_version(&code)
+			.map_err(|e| format!("Invalid runtime to upgrade to: {}", e))?;
+		if new.spec_name != current.spec_name {
+			return Err(format!(
+				"The chain runs the `{}` runtime, not `{}`",
+				current.spec_name, new.spec_name,
+			).into());
+		}
+		println!(
+			"Upgrading `{}` from version {} to {}",
+			new.spec_name, current.spec_version, new.spec_version,
+		);
+		if new.spec_version <= current.spec_version {
+			println!("The chain would refuse this upgrade until `spec_version` is bumped");
+		}
+
+		// The state as `set_code` leaves it, before the first block of the new runtime.
+		storage.top.insert(well_known_keys::CODE.to_vec(), code.clone());
+		let mut ext = BasicExternalities::new(storage);
+		let result = wasm::call(&code, &mut ext, "TryRuntime_on_runtime_upgrade", &[])
+			.map_err(|e| format!(
+				"Cannot run the migrations, is the runtime built with the `try-runtime` feature? \
+				{}",
+				e,
+			))?;
+
+		match <Result<Weight, Vec<u8>>>::decode(&mut &result[..])
+			.map_err(|e| format!("Invalid result of the migrations: {}", e))?
+		{
+			Ok(weight) => {
+				let maximum = MaximumBlockWeight::get();
+				println!(
+					"The migrations succeeded, using a weight of {} ({:.1}% of a block)",
+					weight,
+					weight as f64 * 100.0 / maximum as f64,
+				);
+				if weight > maximum {
+					println!("The first block of the new runtime would be overweight");
+				}
+				Ok(())
+			},
+			Err(e) => Err(format!("The upgrade fails: {}", String::from_utf8_lossy(&e)).into()),
+		}
+	}
+}
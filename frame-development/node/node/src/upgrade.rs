@@ -0,0 +1,199 @@
+//! The `upgrade-runtime` subcommand, upgrading the runtime of a running chain to a Wasm file with a
+//! sudo call, as is otherwise done by hand in Polkadot-JS Apps.
+
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
+use codec::{Decode, Encode};
+use jsonrpc_core::futures::Future;
+use jsonrpc_core_client::{transports::ws, RpcChannel, RpcError};
+use structopt::StructOpt;
+use url::Url;
+use node_template_runtime::{
+	AccountId, Balance, Block, BlockNumber, Call, Hash, Header, Index, SignedExtra,
+	UncheckedExtrinsic, SLOT_DURATION,
+};
+use sc_rpc_api::{author::AuthorClient, chain::ChainClient, state::StateClient};
+use sp_core::{blake2_128, sr25519, storage::StorageKey, Bytes, Pair};
+use sp_rpc::{list::ListOrValue, number::NumberOrHex};
+use sp_runtime::generic::{Era, SignedBlock, SignedPayload};
+use sp_runtime::traits::IdentifyAccount;
+use sp_runtime::MultiSigner;
+use crate::key;
+use crate::storage::storage_prefix;
+use crate::wasm;
+
+type AccountInfo = frame_system::AccountInfo<Index, pallet_balances::AccountData<Balance>>;
+
+/// Upgrade the runtime of a running chain to a Wasm file, with a sudo call.
+#[derive(Debug, StructOpt)]
+pub struct UpgradeRuntimeCmd {
+	/// The Wasm runtime to upgrade to, e.g.
+	/// `target/release/wbuild/node-template-runtime/node_template_runtime.compact.wasm`.
+	#[structopt(long, parse(from_os_str))]
+	pub wasm: PathBuf,
+
+	/// The secret URI of the sudo account, or the path to a file containing it.
+	#[structopt(long)]
+	pub suri: String,
+
+	/// The WebSocket RPC endpoint of a node of the chain.
+	#[structopt(long, default_value = "ws://127.0.0.1:9944")]
+	pub url: String,
+
+	/// How many seconds to wait for the chain to run the new runtime.
+	#[structopt(long, default_value = "120")]
+	pub timeout: u64,
+}
+
+/// The RPC clients of a node, and the event loop driving them.
+struct Node {
+	event_loop: tokio01::runtime::Runtime,
+	author: AuthorClient<Hash, Hash>,
+	chain: ChainClient<BlockNumber, Hash, Header, SignedBlock<Block>>,
+	state: StateClient<Hash>,
+}
+
+impl Node {
+	/// Connect to the node at `url`.
+	fn connect(url: &str) -> Result<Self, String> {
+		let url = Url::parse(url).map_err(|e| format!("Invalid URL `{}`: {}", url, e))?;
+		let mut event_loop = tokio01::runtime::Runtime::new().map_err(|e| e.to_string())?;
+		let channel: RpcChannel = event_loop.block_on(ws::connect(&url))
+			.map_err(|e| format!("Cannot connect to {}: {}", url, e))?;
+
+		Ok(Node {
+			event_loop,
+			author: channel.clone().into(),
+			chain: channel.clone().into(),
+			state: channel.into(),
+		})
+	}
+
+	/// Wait for the result of an RPC call.
+	fn wait<T, F>(&mut self, call: F) -> Result<T, String> where
+		T: Send + 'static,
+		F: Future<Item = T, Error = RpcError> + Send + 'static,
+	{
+		self.event_loop.block_on(call).map_err(|e| format!("RPC call failed: {}", e))
+	}
+
+	/// The value at `key` in the storage of the best block.
+	fn storage<T: Decode>(&mut self, key: Vec<u8>) -> Result<Option<T>, String> {
+		let call = self.state.storage(StorageKey(key), None);
+		self.wait(call)?
+			.map(|data| T::decode(&mut &data.0[..])
+				.map_err(|e| format!("Cannot decode the storage of the node: {}", e)))
+			.transpose()
+	}
+}
+
+impl UpgradeRuntimeCmd {
+	/// Run the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let code = std::fs::read(&self.wasm)?;
+		let new_version = wasm::runtime_version(&code)
+			.map_err(|e| format!("Invalid runtime {}: {}", self.wasm.display(), e))?;
+
+		let suri = key::read_suri(&self.suri)?;
+		let pair = sr25519::Pair::from_string(&suri, None)
+			.map_err(|e| format!("Invalid secret URI: {:?}", e))?;
+		let signer: AccountId = MultiSigner::from(pair.public()).into_account();
+
+		let mut node = Node::connect(&self.url)?;
+
+		let call = node.state.runtime_version(None);
+		let version = node.wait(call)?;
+		if new_version.spec_name != version.spec_name {
+			return Err(format!(
+				"The chain runs the `{}` runtime, not `{}`",
+				version.spec_name, new_version.spec_name,
+			).into());
+		}
+		if new_version.spec_version <= version.spec_version {
+			return Err(format!(
+				"The chain already runs version {} of the runtime, the upgrade is version {}",
+				version.spec_version, new_version.spec_version,
+			).into());
+		}
+
+		let sudo: Option<AccountId> = node.storage(storage_prefix(b"Sudo", b"Key"))?;
+		if sudo.as_ref() != Some(&signer) {
+			return Err(format!("{} is not the sudo account of the chain", signer).into());
+		}
+		let retired: Option<bool> = node.storage(storage_prefix(b"SudoHandover", b"Retired"))?;
+		if retired == Some(true) {
+			return Err("Sudo has been retired, the runtime must be upgraded by governance".into());
+		}
+
+		let call = node.chain.block_hash(Some(ListOrValue::Value(NumberOrHex::Number(0))));
+		let genesis_hash = match node.wait(call)? {
+			ListOrValue::Value(Some(hash)) => hash,
+			_ => return Err("The node has no genesis block".into()),
+		};
+
+		let account_key = [
+			storage_prefix(b"System", b"Account"),
+			blake2_128(&signer.encode()).to_vec(),
+			signer.encode(),
+		].concat();
+		let nonce = node.storage::<AccountInfo>(account_key)?.unwrap_or_default().nonce;
+
+		// `set_code` is weighted at `MaximumBlockWeight`, more than `MaximumExtrinsicWeight` lets
+		// an extrinsic claim. A weight of 0 only bypasses the block weight checks: the upgrade is
+		// not made any cheaper to execute.
+		let call = Call::Sudo(pallet_sudo::Call::sudo_unchecked_weight(
+			Box::new(Call::System(frame_system::Call::set_code(code))),
+			0,
+		));
+		let extra: SignedExtra = (
+			frame_system::CheckSpecVersion::new(),
+			frame_system::CheckTxVersion::new(),
+			frame_system::CheckGenesis::new(),
+			frame_system::CheckEra::from(Era::Immortal),
+			frame_system::CheckNonce::from(nonce),
+			frame_system::CheckWeight::new(),
+			pallet_transaction_payment::ChargeTransactionPayment::from(0),
+		);
+		let additional_signed = (
+			version.spec_version,
+			version.transaction_version,
+			genesis_hash,
+			genesis_hash,
+			(),
+			(),
+			(),
+		);
+		let payload = SignedPayload::from_raw(call, extra, additional_signed);
+		let signature = payload.using_encoded(|payload| pair.sign(payload));
+		let (call, extra, _) = payload.deconstruct();
+		let extrinsic =
+			UncheckedExtrinsic::new_signed(call, signer.into(), signature.into(), extra);
+
+		let call = node.author.submit_extrinsic(Bytes(extrinsic.encode()));
+		let hash = node.wait(call)?;
+		println!(
+			"Submitted the upgrade from version {} to {} in extrinsic {:?}",
+			version.spec_version, new_version.spec_version, hash,
+		);
+
+		// The new runtime records its version when it initializes the block after the upgrade.
+		let deadline = Instant::now() + Duration::from_secs(self.timeout);
+		loop {
+			let upgrade: Option<frame_system::LastRuntimeUpgradeInfo> =
+				node.storage(storage_prefix(b"System", b"LastRuntimeUpgrade"))?;
+			if upgrade.map_or(false, |upgrade| upgrade.spec_version.0 >= new_version.spec_version) {
+				println!("The chain runs version {} of the runtime", new_version.spec_version);
+				return Ok(());
+			}
+			if Instant::now() >= deadline {
+				return Err(format!(
+					"The chain does not run the new runtime after {} seconds, check the `Sudid` \
+					event of extrinsic {:?}",
+					self.timeout, hash,
+				).into());
+			}
+			thread::sleep(Duration::from_millis(SLOT_DURATION));
+		}
+	}
+}
@@ -0,0 +1,48 @@
+//! Execution of runtime Wasm blobs outside of a client, for the subcommands inspecting a chain spec
+//! or a runtime upgrade.
+
+use sc_executor::{NativeExecutor, RuntimeInfo, WasmExecutionMethod};
+use sp_core::NeverNativeValue;
+use sp_core::traits::{CodeExecutor, Externalities, RuntimeCode, WrappedRuntimeCode};
+use sp_state_machine::BasicExternalities;
+use sp_version::RuntimeVersion;
+use crate::service::Executor;
+
+/// An executor never calling into the native runtime, so that `code` is what runs.
+fn executor() -> NativeExecutor<Executor> {
+	NativeExecutor::new(WasmExecutionMethod::Interpreted, None, 8)
+}
+
+/// The runtime code fetched by `fetcher`, for the executor.
+fn runtime_code<'a>(fetcher: &'a WrappedRuntimeCode<'a>) -> RuntimeCode<'a> {
+	RuntimeCode {
+		code_fetcher: fetcher,
+		hash: sp_core::blake2_256(&fetcher.0).to_vec(),
+		heap_pages: None,
+	}
+}
+
+/// The version of the runtime `code`, as returned by its `Core_version`.
+pub fn runtime_version(code: &[u8]) -> Result<RuntimeVersion, String> {
+	let fetcher = WrappedRuntimeCode(code.into());
+	executor()
+		.runtime_version(&mut BasicExternalities::default(), &runtime_code(&fetcher))
+		.map_err(|e| e.to_string())
+}
+
+/// Call the runtime API function `method` of the runtime `code` with the SCALE encoded `data`,
+/// against the state of `ext`. Returns the SCALE encoded result.
+pub fn call(
+	code: &[u8],
+	ext: &mut dyn Externalities,
+	method: &str,
+	data: &[u8],
+) -> Result<Vec<u8>, String> {
+	let fetcher = WrappedRuntimeCode(code.into());
+	let (result, _) = executor().call::<NeverNativeValue, fn() -> _>(
+		ext, &runtime_code(&fetcher), method, data, false, None,
+	);
+	result
+		.map(|result| result.into_encoded())
+		.map_err(|e| e.to_string())
+}
@@ -0,0 +1,93 @@
+//! RPC interface of the fungible assets pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::U256;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use node_template_runtime_api::AssetsApi as AssetsRuntimeApi;
+
+/// Fungible assets RPC methods.
+#[rpc]
+pub trait AssetsApi<BlockHash, AccountId, AssetId, Balance> {
+	/// The balances held by `who`, by asset, at the given block or the best one. Balances are
+	/// hex-encoded when they do not fit in a JSON number.
+	#[rpc(name = "assets_accountBalances")]
+	fn account_balances(
+		&self,
+		who: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<Vec<(AssetId, NumberOrHex)>>;
+}
+
+/// Implements the `AssetsApi` RPC trait by calling into the runtime.
+pub struct Assets<C, Block> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> Assets<C, Block> {
+	/// Create a new `Assets` RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC API.
+pub enum Error {
+	/// The call to the runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block, AccountId, AssetId, Balance>
+	AssetsApi<<Block as BlockT>::Hash, AccountId, AssetId, Balance> for Assets<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: AssetsRuntimeApi<Block, AccountId, AssetId, Balance>,
+	AccountId: Codec,
+	AssetId: Codec,
+	Balance: Codec + Into<U256>,
+{
+	fn account_balances(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(AssetId, NumberOrHex)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		));
+
+		let balances = api.account_balances(&at, who).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query the asset balances.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+
+		Ok(balances.into_iter().map(|(id, balance)| (id, to_number_or_hex(balance.into()))).collect())
+	}
+}
+
+fn to_number_or_hex(n: U256) -> NumberOrHex {
+	if n > U256::from(u64::max_value()) {
+		NumberOrHex::Hex(n)
+	} else {
+		NumberOrHex::Number(n.low_u64())
+	}
+}
@@ -0,0 +1,533 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Fungible assets issued on the chain, in the spirit of ERC-20 tokens.
+///
+/// Anyone can create an asset by reserving `AssetDeposit`, and becomes its owner. The owner mints
+/// new units, freezes and thaws accounts or the whole asset, and describes the asset with a name,
+/// a symbol and a number of decimals, for which a deposit is reserved as well. Holders transfer
+/// and burn their units, and can approve a delegate to transfer up to a given amount on their
+/// behalf.
+
+use sp_std::prelude::*;
+use codec::{Encode, Decode};
+use frame_support::{
+	decl_module, decl_storage, decl_event, decl_error, ensure, dispatch, Parameter,
+	traits::{Currency, Get, ReservableCurrency},
+};
+use frame_system::ensure_signed;
+use sp_runtime::{
+	RuntimeDebug,
+	traits::{
+		AtLeast32BitUnsigned, CheckedAdd, MaybeSerializeDeserialize, Member, Saturating, StaticLookup,
+		Zero,
+	},
+};
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+type DepositBalanceOf<T> =
+	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+
+/// Configure the pallet by specifying the parameters and types on which it depends.
+pub trait Trait: frame_system::Trait {
+	/// Because this pallet emits events, it depends on the runtime's definition of an event.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The units in which asset balances are recorded.
+	type Balance: Member + Parameter + AtLeast32BitUnsigned + Default + Copy
+		+ MaybeSerializeDeserialize;
+
+	/// The identifier of an asset.
+	type AssetId: Member + Parameter + Default + Copy + MaybeSerializeDeserialize;
+
+	/// The currency in which the deposits are reserved.
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// The deposit reserved for creating an asset.
+	type AssetDeposit: Get<DepositBalanceOf<Self>>;
+
+	/// The deposit reserved for setting the metadata of an asset.
+	type MetadataDepositBase: Get<DepositBalanceOf<Self>>;
+
+	/// The additional deposit reserved per byte of the name and symbol of an asset.
+	type MetadataDepositPerByte: Get<DepositBalanceOf<Self>>;
+
+	/// The maximum length of the name and symbol of an asset.
+	type StringLimit: Get<u32>;
+}
+
+/// The state of an asset.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct AssetDetails<AccountId, Balance, DepositBalance> {
+	/// The account allowed to mint, freeze and describe the asset.
+	pub owner: AccountId,
+	/// The total number of units in circulation.
+	pub supply: Balance,
+	/// The deposit reserved from the owner for the asset.
+	pub deposit: DepositBalance,
+	/// Whether all the transfers of the asset are suspended.
+	pub is_frozen: bool,
+}
+
+/// The holding of an account in an asset.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug)]
+pub struct AssetBalance<Balance> {
+	/// The number of units held.
+	pub balance: Balance,
+	/// Whether the account is prevented from moving its units.
+	pub is_frozen: bool,
+}
+
+/// The description of an asset.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug)]
+pub struct AssetMetadata<DepositBalance> {
+	/// The deposit reserved from the owner for the metadata.
+	pub deposit: DepositBalance,
+	/// The name of the asset.
+	pub name: Vec<u8>,
+	/// The ticker symbol of the asset.
+	pub symbol: Vec<u8>,
+	/// The number of decimals used to display a balance.
+	pub decimals: u8,
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Assets {
+		/// The details of each asset.
+		Asset get(fn asset):
+			map hasher(blake2_128_concat) T::AssetId
+			=> Option<AssetDetails<T::AccountId, T::Balance, DepositBalanceOf<T>>>;
+		/// The holdings of each account, by asset.
+		Account get(fn account):
+			double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AssetId
+			=> AssetBalance<T::Balance>;
+		/// The amount a delegate may still transfer out of an account, by asset.
+		Approvals get(fn approval):
+			double_map hasher(blake2_128_concat) T::AssetId,
+				hasher(blake2_128_concat) (T::AccountId, T::AccountId)
+			=> T::Balance;
+		/// The metadata of each asset.
+		Metadata get(fn metadata):
+			map hasher(blake2_128_concat) T::AssetId => AssetMetadata<DepositBalanceOf<T>>;
+	}
+	add_extra_genesis {
+		/// The assets created at genesis: identifier, owner, name, symbol and decimals.
+		config(assets): Vec<(T::AssetId, T::AccountId, Vec<u8>, Vec<u8>, u8)>;
+		/// The units minted at genesis: asset, account and amount.
+		config(balances): Vec<(T::AssetId, T::AccountId, T::Balance)>;
+		build(|config: &GenesisConfig<T>| {
+			for (id, owner, name, symbol, decimals) in &config.assets {
+				assert!(!Asset::<T>::contains_key(id), "Asset id already in use");
+				// Genesis assets are not backed by any deposit.
+				Asset::<T>::insert(id, AssetDetails {
+					owner: owner.clone(),
+					supply: Zero::zero(),
+					deposit: Zero::zero(),
+					is_frozen: false,
+				});
+				Metadata::<T>::insert(id, AssetMetadata {
+					deposit: Zero::zero(),
+					name: name.clone(),
+					symbol: symbol.clone(),
+					decimals: *decimals,
+				});
+			}
+			for (id, who, amount) in &config.balances {
+				Asset::<T>::mutate(id, |details| {
+					let details = details.as_mut().expect("Balances of an unknown asset");
+					details.supply = details.supply.checked_add(amount).expect("Supply overflow");
+				});
+				Account::<T>::mutate(who, id, |account| account.balance += *amount);
+			}
+		});
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where
+		AccountId = <T as frame_system::Trait>::AccountId,
+		Balance = <T as Trait>::Balance,
+		AssetId = <T as Trait>::AssetId,
+	{
+		/// An asset was created. [asset, owner]
+		Created(AssetId, AccountId),
+		/// Units of an asset were minted. [asset, beneficiary, amount]
+		Issued(AssetId, AccountId, Balance),
+		/// Units of an asset were burned. [asset, holder, amount]
+		Burned(AssetId, AccountId, Balance),
+		/// Units of an asset were transferred. [asset, from, to, amount]
+		Transferred(AssetId, AccountId, AccountId, Balance),
+		/// An account was frozen. [asset, who]
+		Frozen(AssetId, AccountId),
+		/// An account was thawed. [asset, who]
+		Thawed(AssetId, AccountId),
+		/// All the transfers of an asset were suspended. [asset]
+		AssetFrozen(AssetId),
+		/// The transfers of an asset were resumed. [asset]
+		AssetThawed(AssetId),
+		/// A delegate was approved to transfer units out of an account. [asset, owner, delegate, amount]
+		ApprovedTransfer(AssetId, AccountId, AccountId, Balance),
+		/// A delegate transferred units out of an account. [asset, owner, delegate, destination, amount]
+		TransferredApproved(AssetId, AccountId, AccountId, AccountId, Balance),
+		/// The metadata of an asset was set. [asset, name, symbol, decimals]
+		MetadataSet(AssetId, Vec<u8>, Vec<u8>, u8),
+		/// The metadata of an asset was cleared. [asset]
+		MetadataCleared(AssetId),
+	}
+);
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The asset identifier is already taken.
+		InUse,
+		/// The asset does not exist.
+		Unknown,
+		/// The signer is not the owner of the asset.
+		NoPermission,
+		/// The amount is zero.
+		AmountZero,
+		/// The account does not hold enough units.
+		BalanceLow,
+		/// The supply or a balance would overflow.
+		Overflow,
+		/// The account or the asset is frozen.
+		Frozen,
+		/// The delegate was not approved for that many units.
+		Unapproved,
+		/// The name or symbol is too long.
+		BadMetadata,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// Create an asset owned by the signer, reserving `AssetDeposit` from it.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 2)]
+		pub fn create(origin, id: T::AssetId) -> dispatch::DispatchResult {
+			let owner = ensure_signed(origin)?;
+			ensure!(!Asset::<T>::contains_key(id), Error::<T>::InUse);
+
+			let deposit = T::AssetDeposit::get();
+			T::Currency::reserve(&owner, deposit)?;
+
+			Asset::<T>::insert(id, AssetDetails {
+				owner: owner.clone(),
+				supply: Zero::zero(),
+				deposit,
+				is_frozen: false,
+			});
+			Self::deposit_event(RawEvent::Created(id, owner));
+			Ok(())
+		}
+
+		/// Mint `amount` units of an asset to `beneficiary`. Only the owner of the asset can mint.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(2, 2)]
+		pub fn mint(
+			origin,
+			id: T::AssetId,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+			#[compact] amount: T::Balance,
+		) -> dispatch::DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			ensure!(!amount.is_zero(), Error::<T>::AmountZero);
+
+			Asset::<T>::try_mutate(id, |maybe_details| -> dispatch::DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(details.owner == origin, Error::<T>::NoPermission);
+				details.supply = details.supply.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+
+				// The supply bounds every balance, so this cannot overflow.
+				Account::<T>::mutate(&beneficiary, id, |account| account.balance += amount);
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::Issued(id, beneficiary, amount));
+			Ok(())
+		}
+
+		/// Burn `amount` of the signer's units of an asset.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(2, 2)]
+		pub fn burn(origin, id: T::AssetId, #[compact] amount: T::Balance) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::AmountZero);
+
+			Asset::<T>::try_mutate(id, |maybe_details| -> dispatch::DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(!details.is_frozen, Error::<T>::Frozen);
+				Self::debit(&who, id, amount)?;
+				details.supply = details.supply.saturating_sub(amount);
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::Burned(id, who, amount));
+			Ok(())
+		}
+
+		/// Transfer `amount` of the signer's units of an asset to `target`.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(3, 2)]
+		pub fn transfer(
+			origin,
+			id: T::AssetId,
+			target: <T::Lookup as StaticLookup>::Source,
+			#[compact] amount: T::Balance,
+		) -> dispatch::DispatchResult {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(target)?;
+
+			Self::do_transfer(id, &from, &to, amount)?;
+			Self::deposit_event(RawEvent::Transferred(id, from, to, amount));
+			Ok(())
+		}
+
+		/// Prevent `who` from moving its units of an asset. Only the owner of the asset can freeze.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(2, 1)]
+		pub fn freeze(
+			origin,
+			id: T::AssetId,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> dispatch::DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			Self::ensure_owner(id, &origin)?;
+
+			Account::<T>::mutate(&who, id, |account| account.is_frozen = true);
+			Self::deposit_event(RawEvent::Frozen(id, who));
+			Ok(())
+		}
+
+		/// Allow a frozen account to move its units of an asset again.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(2, 1)]
+		pub fn thaw(
+			origin,
+			id: T::AssetId,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> dispatch::DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			Self::ensure_owner(id, &origin)?;
+
+			let mut account = Account::<T>::get(&who, id);
+			account.is_frozen = false;
+			Self::store(&who, id, account);
+			Self::deposit_event(RawEvent::Thawed(id, who));
+			Ok(())
+		}
+
+		/// Suspend all the transfers and burns of an asset.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+		pub fn freeze_asset(origin, id: T::AssetId) -> dispatch::DispatchResult {
+			let origin = ensure_signed(origin)?;
+			Self::set_asset_frozen(id, &origin, true)?;
+			Self::deposit_event(RawEvent::AssetFrozen(id));
+			Ok(())
+		}
+
+		/// Resume the transfers and burns of a frozen asset.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+		pub fn thaw_asset(origin, id: T::AssetId) -> dispatch::DispatchResult {
+			let origin = ensure_signed(origin)?;
+			Self::set_asset_frozen(id, &origin, false)?;
+			Self::deposit_event(RawEvent::AssetThawed(id));
+			Ok(())
+		}
+
+		/// Allow `delegate` to transfer up to `amount` of the signer's units of an asset, replacing
+		/// any previous approval. An amount of zero cancels the approval.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+		pub fn approve_transfer(
+			origin,
+			id: T::AssetId,
+			delegate: <T::Lookup as StaticLookup>::Source,
+			#[compact] amount: T::Balance,
+		) -> dispatch::DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+			ensure!(Asset::<T>::contains_key(id), Error::<T>::Unknown);
+
+			let key = (owner.clone(), delegate.clone());
+			if amount.is_zero() {
+				Approvals::<T>::remove(id, key);
+			} else {
+				Approvals::<T>::insert(id, key, amount);
+			}
+			Self::deposit_event(RawEvent::ApprovedTransfer(id, owner, delegate, amount));
+			Ok(())
+		}
+
+		/// Transfer `amount` units of an asset from `owner` to `destination`, out of the approval
+		/// `owner` gave to the signer.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(4, 3)]
+		pub fn transfer_approved(
+			origin,
+			id: T::AssetId,
+			owner: <T::Lookup as StaticLookup>::Source,
+			destination: <T::Lookup as StaticLookup>::Source,
+			#[compact] amount: T::Balance,
+		) -> dispatch::DispatchResult {
+			let delegate = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+			let destination = T::Lookup::lookup(destination)?;
+
+			let key = (owner.clone(), delegate.clone());
+			let approved = Approvals::<T>::get(id, &key);
+			ensure!(!approved.is_zero() && approved >= amount, Error::<T>::Unapproved);
+
+			Self::do_transfer(id, &owner, &destination, amount)?;
+			let remaining = approved - amount;
+			if remaining.is_zero() {
+				Approvals::<T>::remove(id, &key);
+			} else {
+				Approvals::<T>::insert(id, &key, remaining);
+			}
+			Self::deposit_event(RawEvent::TransferredApproved(id, owner, delegate, destination, amount));
+			Ok(())
+		}
+
+		/// Set the name, symbol and decimals of an asset. Only the owner of the asset can set them,
+		/// and a deposit depending on their length is reserved from it.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(2, 2)]
+		pub fn set_metadata(
+			origin,
+			id: T::AssetId,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			decimals: u8,
+		) -> dispatch::DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let limit = T::StringLimit::get() as usize;
+			ensure!(name.len() <= limit && symbol.len() <= limit, Error::<T>::BadMetadata);
+			Self::ensure_owner(id, &origin)?;
+
+			Metadata::<T>::try_mutate(id, |metadata| -> dispatch::DispatchResult {
+				let bytes = (name.len() + symbol.len()) as u32;
+				let deposit = T::MetadataDepositPerByte::get()
+					.saturating_mul(bytes.into())
+					.saturating_add(T::MetadataDepositBase::get());
+
+				if deposit > metadata.deposit {
+					T::Currency::reserve(&origin, deposit - metadata.deposit)?;
+				} else {
+					T::Currency::unreserve(&origin, metadata.deposit - deposit);
+				}
+
+				*metadata = AssetMetadata { deposit, name: name.clone(), symbol: symbol.clone(), decimals };
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::MetadataSet(id, name, symbol, decimals));
+			Ok(())
+		}
+
+		/// Clear the metadata of an asset and return its deposit to the owner.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(2, 2)]
+		pub fn clear_metadata(origin, id: T::AssetId) -> dispatch::DispatchResult {
+			let origin = ensure_signed(origin)?;
+			Self::ensure_owner(id, &origin)?;
+
+			let metadata = Metadata::<T>::take(id);
+			T::Currency::unreserve(&origin, metadata.deposit);
+			Self::deposit_event(RawEvent::MetadataCleared(id));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// The balances held by `who`, by asset.
+	pub fn account_balances(who: &T::AccountId) -> Vec<(T::AssetId, T::Balance)> {
+		Account::<T>::iter_prefix(who)
+			.filter(|(_, account)| !account.balance.is_zero())
+			.map(|(id, account)| (id, account.balance))
+			.collect()
+	}
+
+	fn ensure_owner(id: T::AssetId, who: &T::AccountId) -> dispatch::DispatchResult {
+		let details = Asset::<T>::get(id).ok_or(Error::<T>::Unknown)?;
+		ensure!(&details.owner == who, Error::<T>::NoPermission);
+		Ok(())
+	}
+
+	fn set_asset_frozen(id: T::AssetId, who: &T::AccountId, frozen: bool) -> dispatch::DispatchResult {
+		Asset::<T>::try_mutate(id, |maybe_details| -> dispatch::DispatchResult {
+			let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+			ensure!(&details.owner == who, Error::<T>::NoPermission);
+			details.is_frozen = frozen;
+			Ok(())
+		})
+	}
+
+	/// Store the holding of `who`, removing it once it is empty and not frozen.
+	fn store(who: &T::AccountId, id: T::AssetId, account: AssetBalance<T::Balance>) {
+		if account.balance.is_zero() && !account.is_frozen {
+			Account::<T>::remove(who, id);
+		} else {
+			Account::<T>::insert(who, id, account);
+		}
+	}
+
+	/// Take `amount` units out of the balance of `who`.
+	fn debit(who: &T::AccountId, id: T::AssetId, amount: T::Balance) -> dispatch::DispatchResult {
+		let mut account = Account::<T>::get(who, id);
+		ensure!(!account.is_frozen, Error::<T>::Frozen);
+		ensure!(account.balance >= amount, Error::<T>::BalanceLow);
+
+		account.balance -= amount;
+		Self::store(who, id, account);
+		Ok(())
+	}
+
+	fn do_transfer(
+		id: T::AssetId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: T::Balance,
+	) -> dispatch::DispatchResult {
+		ensure!(!amount.is_zero(), Error::<T>::AmountZero);
+		let details = Asset::<T>::get(id).ok_or(Error::<T>::Unknown)?;
+		ensure!(!details.is_frozen, Error::<T>::Frozen);
+
+		let balance = Account::<T>::get(from, id);
+		ensure!(!balance.is_frozen, Error::<T>::Frozen);
+		ensure!(balance.balance >= amount, Error::<T>::BalanceLow);
+		if from == to {
+			return Ok(());
+		}
+
+		Self::debit(from, id, amount)?;
+		// The supply bounds every balance, so this cannot overflow.
+		Account::<T>::mutate(to, id, |account| account.balance += amount);
+		Ok(())
+	}
+}
+
+#[cfg(any(feature = "try-runtime", test))]
+impl<T: Trait> Module<T> {
+	/// Check that every holding is of an existing asset and that the supply of each asset is the
+	/// sum of its holdings. Run before and after the migrations of a runtime upgrade by
+	/// `try-runtime`.
+	pub fn check_state() -> Result<(), &'static str> {
+		let mut held = Vec::<(T::AssetId, T::Balance)>::new();
+		for (_, id, account) in Account::<T>::iter() {
+			ensure!(Asset::<T>::contains_key(id), "A holding is of an unknown asset");
+			match held.iter_mut().find(|(asset, _)| *asset == id) {
+				Some((_, total)) => *total = total.saturating_add(account.balance),
+				None => held.push((id, account.balance)),
+			}
+		}
+		for (id, details) in Asset::<T>::iter() {
+			let total = held.iter().find(|(asset, _)| *asset == id)
+				.map_or_else(Zero::zero, |(_, total)| *total);
+			ensure!(
+				details.supply == total,
+				"The supply of an asset is not the sum of its holdings",
+			);
+		}
+		Ok(())
+	}
+}
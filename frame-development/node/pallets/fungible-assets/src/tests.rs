@@ -0,0 +1,215 @@
+use crate::{AssetMetadata, Error, RawEvent, mock::*};
+use frame_support::{assert_ok, assert_noop};
+use sp_runtime::DispatchError;
+
+fn balance(id: u32, who: u64) -> u64 {
+	Assets::account(who, id).balance
+}
+
+fn supply(id: u32) -> u64 {
+	Assets::asset(id).expect("the asset exists").supply
+}
+
+fn last_event() -> TestEvent {
+	System::events().pop().expect("an event was deposited").event
+}
+
+#[test]
+fn genesis_assets_are_created_with_their_balances() {
+	new_test_ext().execute_with(|| {
+		let details = Assets::asset(GENESIS_ASSET).unwrap();
+		assert_eq!(details.owner, 1);
+		assert_eq!(details.supply, 800);
+		assert_eq!(details.deposit, 0);
+		assert_eq!(Assets::metadata(GENESIS_ASSET), AssetMetadata {
+			deposit: 0,
+			name: b"Genesis".to_vec(),
+			symbol: b"GEN".to_vec(),
+			decimals: 12,
+		});
+		assert_eq!(Assets::account_balances(&2), vec![(GENESIS_ASSET, 300)]);
+	});
+}
+
+#[test]
+fn creating_an_asset_reserves_a_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::create(Origin::signed(3), 0));
+		assert_eq!(Balances::reserved_balance(3), 100);
+		assert_eq!(last_event(), TestEvent::assets(RawEvent::Created(0, 3)));
+
+		assert_noop!(Assets::create(Origin::signed(2), 0), Error::<Test>::InUse);
+	});
+}
+
+#[test]
+fn only_the_owner_can_mint() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Assets::mint(Origin::signed(2), GENESIS_ASSET, 2, 10), Error::<Test>::NoPermission);
+		assert_noop!(Assets::mint(Origin::signed(1), 0, 2, 10), Error::<Test>::Unknown);
+
+		assert_ok!(Assets::mint(Origin::signed(1), GENESIS_ASSET, 3, 10));
+		assert_eq!(balance(GENESIS_ASSET, 3), 10);
+		assert_eq!(supply(GENESIS_ASSET), 810);
+		assert_eq!(last_event(), TestEvent::assets(RawEvent::Issued(GENESIS_ASSET, 3, 10)));
+
+		assert_noop!(
+			Assets::mint(Origin::signed(1), GENESIS_ASSET, 3, u64::max_value()),
+			Error::<Test>::Overflow
+		);
+	});
+}
+
+#[test]
+fn burning_reduces_the_supply() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Assets::burn(Origin::signed(2), GENESIS_ASSET, 301), Error::<Test>::BalanceLow);
+
+		assert_ok!(Assets::burn(Origin::signed(2), GENESIS_ASSET, 300));
+		assert_eq!(balance(GENESIS_ASSET, 2), 0);
+		assert_eq!(supply(GENESIS_ASSET), 500);
+		assert!(Assets::account_balances(&2).is_empty());
+	});
+}
+
+#[test]
+fn transfers_move_units_between_accounts() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::transfer(Origin::signed(1), GENESIS_ASSET, 3, 200));
+		assert_eq!(balance(GENESIS_ASSET, 1), 300);
+		assert_eq!(balance(GENESIS_ASSET, 3), 200);
+		assert_eq!(supply(GENESIS_ASSET), 800);
+		assert_eq!(
+			last_event(),
+			TestEvent::assets(RawEvent::Transferred(GENESIS_ASSET, 1, 3, 200)),
+		);
+
+		assert_noop!(Assets::transfer(Origin::signed(3), GENESIS_ASSET, 1, 201), Error::<Test>::BalanceLow);
+		assert_noop!(Assets::transfer(Origin::signed(3), GENESIS_ASSET, 1, 0), Error::<Test>::AmountZero);
+		assert_noop!(Assets::transfer(Origin::signed(3), 0, 1, 1), Error::<Test>::Unknown);
+	});
+}
+
+#[test]
+fn frozen_accounts_cannot_move_units() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Assets::freeze(Origin::signed(2), GENESIS_ASSET, 2), Error::<Test>::NoPermission);
+		assert_ok!(Assets::freeze(Origin::signed(1), GENESIS_ASSET, 2));
+
+		assert_noop!(Assets::transfer(Origin::signed(2), GENESIS_ASSET, 3, 1), Error::<Test>::Frozen);
+		assert_noop!(Assets::burn(Origin::signed(2), GENESIS_ASSET, 1), Error::<Test>::Frozen);
+		// A frozen account can still receive units.
+		assert_ok!(Assets::transfer(Origin::signed(1), GENESIS_ASSET, 2, 1));
+
+		assert_ok!(Assets::thaw(Origin::signed(1), GENESIS_ASSET, 2));
+		assert_ok!(Assets::transfer(Origin::signed(2), GENESIS_ASSET, 3, 1));
+	});
+}
+
+#[test]
+fn frozen_assets_cannot_be_moved_by_anyone() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::freeze_asset(Origin::signed(1), GENESIS_ASSET));
+		assert_eq!(last_event(), TestEvent::assets(RawEvent::AssetFrozen(GENESIS_ASSET)));
+
+		assert_noop!(Assets::transfer(Origin::signed(1), GENESIS_ASSET, 3, 1), Error::<Test>::Frozen);
+		assert_noop!(Assets::burn(Origin::signed(2), GENESIS_ASSET, 1), Error::<Test>::Frozen);
+
+		assert_ok!(Assets::thaw_asset(Origin::signed(1), GENESIS_ASSET));
+		assert_ok!(Assets::transfer(Origin::signed(1), GENESIS_ASSET, 3, 1));
+	});
+}
+
+#[test]
+fn delegates_transfer_up_to_the_approved_amount() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Assets::transfer_approved(Origin::signed(3), GENESIS_ASSET, 1, 3, 1),
+			Error::<Test>::Unapproved
+		);
+
+		assert_ok!(Assets::approve_transfer(Origin::signed(1), GENESIS_ASSET, 3, 100));
+		assert_ok!(Assets::transfer_approved(Origin::signed(3), GENESIS_ASSET, 1, 2, 60));
+		assert_eq!(balance(GENESIS_ASSET, 2), 360);
+		assert_eq!(Assets::approval(GENESIS_ASSET, (1, 3)), 40);
+		assert_eq!(
+			last_event(),
+			TestEvent::assets(RawEvent::TransferredApproved(GENESIS_ASSET, 1, 3, 2, 60)),
+		);
+
+		assert_noop!(
+			Assets::transfer_approved(Origin::signed(3), GENESIS_ASSET, 1, 2, 41),
+			Error::<Test>::Unapproved
+		);
+		assert_ok!(Assets::transfer_approved(Origin::signed(3), GENESIS_ASSET, 1, 3, 40));
+		assert!(!crate::Approvals::<Test>::contains_key(GENESIS_ASSET, (1, 3)));
+	});
+}
+
+#[test]
+fn approvals_are_replaced_and_cancelled() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::approve_transfer(Origin::signed(1), GENESIS_ASSET, 3, 100));
+		assert_ok!(Assets::approve_transfer(Origin::signed(1), GENESIS_ASSET, 3, 10));
+		assert_eq!(Assets::approval(GENESIS_ASSET, (1, 3)), 10);
+
+		assert_ok!(Assets::approve_transfer(Origin::signed(1), GENESIS_ASSET, 3, 0));
+		assert_noop!(
+			Assets::transfer_approved(Origin::signed(3), GENESIS_ASSET, 1, 3, 1),
+			Error::<Test>::Unapproved
+		);
+	});
+}
+
+#[test]
+fn metadata_deposit_follows_its_length() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::create(Origin::signed(3), 0));
+		assert_noop!(
+			Assets::set_metadata(Origin::signed(2), 0, b"Token".to_vec(), b"TKN".to_vec(), 18),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			Assets::set_metadata(Origin::signed(3), 0, vec![0; 17], b"TKN".to_vec(), 18),
+			Error::<Test>::BadMetadata
+		);
+
+		assert_ok!(Assets::set_metadata(Origin::signed(3), 0, b"Token".to_vec(), b"TKN".to_vec(), 18));
+		assert_eq!(Balances::reserved_balance(3), 100 + 10 + 8);
+		assert_eq!(
+			last_event(),
+			TestEvent::assets(RawEvent::MetadataSet(0, b"Token".to_vec(), b"TKN".to_vec(), 18)),
+		);
+
+		assert_ok!(Assets::set_metadata(Origin::signed(3), 0, b"T".to_vec(), b"T".to_vec(), 18));
+		assert_eq!(Balances::reserved_balance(3), 100 + 10 + 2);
+
+		assert_ok!(Assets::clear_metadata(Origin::signed(3), 0));
+		assert_eq!(Balances::reserved_balance(3), 100);
+		assert_eq!(Assets::metadata(0), Default::default());
+	});
+}
+
+#[test]
+fn signed_origin_is_required() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Assets::create(Origin::root(), 0), DispatchError::BadOrigin);
+		assert_noop!(Assets::transfer(Origin::none(), GENESIS_ASSET, 3, 1), DispatchError::BadOrigin);
+	});
+}
+
+#[test]
+fn check_state_finds_a_supply_that_is_not_the_sum_of_the_holdings() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::create(Origin::signed(3), 0));
+		assert_ok!(Assets::mint(Origin::signed(3), 0, 4, 50));
+		assert_ok!(Assets::transfer(Origin::signed(2), GENESIS_ASSET, 4, 100));
+		assert_ok!(Assets::check_state());
+
+		crate::Asset::<Test>::mutate(0, |details| details.as_mut().unwrap().supply += 1);
+		assert_eq!(
+			Assets::check_state(),
+			Err("The supply of an asset is not the sum of its holdings"),
+		);
+	});
+}
@@ -0,0 +1,81 @@
+//! RPC interface of the NFT pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use node_template_runtime_api::NftApi as NftRuntimeApi;
+
+/// NFT RPC methods.
+#[rpc]
+pub trait NftApi<BlockHash, AccountId, CollectionId, ItemId> {
+	/// The items held by `who`, by collection and item identifier, at the given block or the
+	/// best one.
+	#[rpc(name = "nft_ownedItems")]
+	fn owned_items(
+		&self,
+		who: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<Vec<(CollectionId, ItemId)>>;
+}
+
+/// Implements the `NftApi` RPC trait by calling into the runtime.
+pub struct Nft<C, Block> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> Nft<C, Block> {
+	/// Create a new `Nft` RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC API.
+pub enum Error {
+	/// The call to the runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block, AccountId, CollectionId, ItemId>
+	NftApi<<Block as BlockT>::Hash, AccountId, CollectionId, ItemId> for Nft<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: NftRuntimeApi<Block, AccountId, CollectionId, ItemId>,
+	AccountId: Codec,
+	CollectionId: Codec,
+	ItemId: Codec,
+{
+	fn owned_items(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(CollectionId, ItemId)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		));
+
+		api.owned(&at, who).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query the owned items.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+}
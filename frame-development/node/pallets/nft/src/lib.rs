@@ -0,0 +1,384 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Non-fungible tokens, grouped in collections.
+///
+/// Anyone can create a collection by reserving `CollectionDeposit`, and becomes its owner. The
+/// owner appoints admins, and together they mint items to any account and attach metadata to
+/// them. The metadata deposit depends on its length and is always reserved from the collection
+/// owner. Item owners transfer and burn their items.
+
+use sp_std::prelude::*;
+use codec::{Encode, Decode};
+use frame_support::{
+	decl_module, decl_storage, decl_event, decl_error, ensure, dispatch, Parameter,
+	traits::{Currency, Get, ReservableCurrency},
+};
+use frame_system::ensure_signed;
+use sp_runtime::{
+	RuntimeDebug,
+	traits::{MaybeSerializeDeserialize, Member, Saturating, StaticLookup},
+};
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+type DepositBalanceOf<T> =
+	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+
+/// Configure the pallet by specifying the parameters and types on which it depends.
+pub trait Trait: frame_system::Trait {
+	/// Because this pallet emits events, it depends on the runtime's definition of an event.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The identifier of a collection.
+	type CollectionId: Member + Parameter + Default + Copy + MaybeSerializeDeserialize;
+
+	/// The identifier of an item within its collection.
+	type ItemId: Member + Parameter + Default + Copy + MaybeSerializeDeserialize;
+
+	/// The currency in which the deposits are reserved.
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// The deposit reserved for creating a collection.
+	type CollectionDeposit: Get<DepositBalanceOf<Self>>;
+
+	/// The deposit reserved for the metadata of an item.
+	type MetadataDepositBase: Get<DepositBalanceOf<Self>>;
+
+	/// The additional deposit reserved per byte of the metadata of an item.
+	type MetadataDepositPerByte: Get<DepositBalanceOf<Self>>;
+
+	/// The maximum length of the metadata of an item.
+	type StringLimit: Get<u32>;
+}
+
+/// The state of a collection.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct CollectionDetails<AccountId, DepositBalance> {
+	/// The account allowed to appoint admins, mint items and describe them.
+	pub owner: AccountId,
+	/// The deposit reserved from the owner for the collection.
+	pub deposit: DepositBalance,
+	/// The number of items in the collection.
+	pub items: u32,
+}
+
+/// The state of an item.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct ItemDetails<AccountId> {
+	/// The account holding the item.
+	pub owner: AccountId,
+}
+
+/// The description of an item.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug)]
+pub struct ItemMetadata<DepositBalance> {
+	/// The deposit reserved from the collection owner for the metadata.
+	pub deposit: DepositBalance,
+	/// The metadata itself, typically a JSON document or a link to one.
+	pub data: Vec<u8>,
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Nft {
+		/// The details of each collection.
+		Collection get(fn collection):
+			map hasher(blake2_128_concat) T::CollectionId
+			=> Option<CollectionDetails<T::AccountId, DepositBalanceOf<T>>>;
+		/// The admins of each collection, besides its owner.
+		Admins get(fn is_admin):
+			double_map hasher(blake2_128_concat) T::CollectionId, hasher(blake2_128_concat) T::AccountId
+			=> bool;
+		/// The details of each item, by collection.
+		Item get(fn item):
+			double_map hasher(blake2_128_concat) T::CollectionId, hasher(blake2_128_concat) T::ItemId
+			=> Option<ItemDetails<T::AccountId>>;
+		/// The metadata of each item, by collection.
+		Metadata get(fn metadata):
+			double_map hasher(blake2_128_concat) T::CollectionId, hasher(blake2_128_concat) T::ItemId
+			=> ItemMetadata<DepositBalanceOf<T>>;
+		/// The items held by each account.
+		Account:
+			double_map hasher(blake2_128_concat) T::AccountId,
+				hasher(blake2_128_concat) (T::CollectionId, T::ItemId)
+			=> ();
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where
+		AccountId = <T as frame_system::Trait>::AccountId,
+		CollectionId = <T as Trait>::CollectionId,
+		ItemId = <T as Trait>::ItemId,
+	{
+		/// A collection was created. [collection, owner]
+		Created(CollectionId, AccountId),
+		/// An admin was appointed to a collection. [collection, admin]
+		AdminAdded(CollectionId, AccountId),
+		/// An admin of a collection was dismissed. [collection, admin]
+		AdminRemoved(CollectionId, AccountId),
+		/// An item was minted. [collection, item, owner]
+		Issued(CollectionId, ItemId, AccountId),
+		/// An item was transferred. [collection, item, from, to]
+		Transferred(CollectionId, ItemId, AccountId, AccountId),
+		/// An item was burned. [collection, item, owner]
+		Burned(CollectionId, ItemId, AccountId),
+		/// The metadata of an item was set. [collection, item, data]
+		MetadataSet(CollectionId, ItemId, Vec<u8>),
+		/// The metadata of an item was cleared. [collection, item]
+		MetadataCleared(CollectionId, ItemId),
+	}
+);
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The collection identifier is already taken.
+		InUse,
+		/// The collection does not exist.
+		UnknownCollection,
+		/// The item does not exist.
+		UnknownItem,
+		/// The item identifier is already taken in the collection.
+		AlreadyExists,
+		/// The signer is not allowed to do this.
+		NoPermission,
+		/// The metadata is too long.
+		BadMetadata,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// Create a collection owned by the signer, reserving `CollectionDeposit` from it.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 2)]
+		pub fn create(origin, collection: T::CollectionId) -> dispatch::DispatchResult {
+			let owner = ensure_signed(origin)?;
+			ensure!(!Collection::<T>::contains_key(collection), Error::<T>::InUse);
+
+			let deposit = T::CollectionDeposit::get();
+			T::Currency::reserve(&owner, deposit)?;
+
+			Collection::<T>::insert(collection, CollectionDetails {
+				owner: owner.clone(),
+				deposit,
+				items: 0,
+			});
+			Self::deposit_event(RawEvent::Created(collection, owner));
+			Ok(())
+		}
+
+		/// Appoint `admin` to mint and describe the items of a collection. Only the owner of the
+		/// collection can appoint admins.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+		pub fn add_admin(
+			origin,
+			collection: T::CollectionId,
+			admin: <T::Lookup as StaticLookup>::Source,
+		) -> dispatch::DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let admin = T::Lookup::lookup(admin)?;
+			Self::ensure_owner(collection, &origin)?;
+
+			Admins::<T>::insert(collection, &admin, true);
+			Self::deposit_event(RawEvent::AdminAdded(collection, admin));
+			Ok(())
+		}
+
+		/// Dismiss an admin of a collection.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+		pub fn remove_admin(
+			origin,
+			collection: T::CollectionId,
+			admin: <T::Lookup as StaticLookup>::Source,
+		) -> dispatch::DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let admin = T::Lookup::lookup(admin)?;
+			Self::ensure_owner(collection, &origin)?;
+
+			Admins::<T>::remove(collection, &admin);
+			Self::deposit_event(RawEvent::AdminRemoved(collection, admin));
+			Ok(())
+		}
+
+		/// Mint `item` in a collection to `owner`. Only the owner and the admins of the collection
+		/// can mint.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(3, 3)]
+		pub fn mint(
+			origin,
+			collection: T::CollectionId,
+			item: T::ItemId,
+			owner: <T::Lookup as StaticLookup>::Source,
+		) -> dispatch::DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+
+			Collection::<T>::try_mutate(collection, |maybe_details| -> dispatch::DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::UnknownCollection)?;
+				ensure!(Self::can_manage(collection, details, &origin), Error::<T>::NoPermission);
+				ensure!(!Item::<T>::contains_key(collection, item), Error::<T>::AlreadyExists);
+
+				details.items = details.items.saturating_add(1);
+				Item::<T>::insert(collection, item, ItemDetails { owner: owner.clone() });
+				Account::<T>::insert(&owner, (collection, item), ());
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::Issued(collection, item, owner));
+			Ok(())
+		}
+
+		/// Transfer an item held by the signer to `dest`.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 3)]
+		pub fn transfer(
+			origin,
+			collection: T::CollectionId,
+			item: T::ItemId,
+			dest: <T::Lookup as StaticLookup>::Source,
+		) -> dispatch::DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let dest = T::Lookup::lookup(dest)?;
+
+			Item::<T>::try_mutate(collection, item, |maybe_details| -> dispatch::DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::UnknownItem)?;
+				ensure!(details.owner == origin, Error::<T>::NoPermission);
+
+				Account::<T>::remove(&origin, (collection, item));
+				Account::<T>::insert(&dest, (collection, item), ());
+				details.owner = dest.clone();
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::Transferred(collection, item, origin, dest));
+			Ok(())
+		}
+
+		/// Burn an item held by the signer. The deposit of its metadata returns to the collection
+		/// owner.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(3, 4)]
+		pub fn burn(origin, collection: T::CollectionId, item: T::ItemId) -> dispatch::DispatchResult {
+			let origin = ensure_signed(origin)?;
+
+			let details = Item::<T>::get(collection, item).ok_or(Error::<T>::UnknownItem)?;
+			ensure!(details.owner == origin, Error::<T>::NoPermission);
+
+			Collection::<T>::mutate(collection, |maybe_details| {
+				if let Some(collection_details) = maybe_details {
+					collection_details.items = collection_details.items.saturating_sub(1);
+					let metadata = Metadata::<T>::take(collection, item);
+					T::Currency::unreserve(&collection_details.owner, metadata.deposit);
+				}
+			});
+			Item::<T>::remove(collection, item);
+			Account::<T>::remove(&origin, (collection, item));
+
+			Self::deposit_event(RawEvent::Burned(collection, item, origin));
+			Ok(())
+		}
+
+		/// Set the metadata of an item. Only the owner and the admins of the collection can set
+		/// it, and a deposit depending on its length is reserved from the collection owner.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(3, 2)]
+		pub fn set_metadata(
+			origin,
+			collection: T::CollectionId,
+			item: T::ItemId,
+			data: Vec<u8>,
+		) -> dispatch::DispatchResult {
+			let origin = ensure_signed(origin)?;
+			ensure!(data.len() <= T::StringLimit::get() as usize, Error::<T>::BadMetadata);
+			let details = Collection::<T>::get(collection).ok_or(Error::<T>::UnknownCollection)?;
+			ensure!(Self::can_manage(collection, &details, &origin), Error::<T>::NoPermission);
+			ensure!(Item::<T>::contains_key(collection, item), Error::<T>::UnknownItem);
+
+			Metadata::<T>::try_mutate(collection, item, |metadata| -> dispatch::DispatchResult {
+				let deposit = T::MetadataDepositPerByte::get()
+					.saturating_mul((data.len() as u32).into())
+					.saturating_add(T::MetadataDepositBase::get());
+
+				if deposit > metadata.deposit {
+					T::Currency::reserve(&details.owner, deposit - metadata.deposit)?;
+				} else {
+					T::Currency::unreserve(&details.owner, metadata.deposit - deposit);
+				}
+
+				*metadata = ItemMetadata { deposit, data: data.clone() };
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::MetadataSet(collection, item, data));
+			Ok(())
+		}
+
+		/// Clear the metadata of an item and return its deposit to the collection owner.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(3, 2)]
+		pub fn clear_metadata(
+			origin,
+			collection: T::CollectionId,
+			item: T::ItemId,
+		) -> dispatch::DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let details = Collection::<T>::get(collection).ok_or(Error::<T>::UnknownCollection)?;
+			ensure!(Self::can_manage(collection, &details, &origin), Error::<T>::NoPermission);
+			ensure!(Item::<T>::contains_key(collection, item), Error::<T>::UnknownItem);
+
+			let metadata = Metadata::<T>::take(collection, item);
+			T::Currency::unreserve(&details.owner, metadata.deposit);
+			Self::deposit_event(RawEvent::MetadataCleared(collection, item));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// The items held by `who`, by collection and item identifier.
+	pub fn owned(who: &T::AccountId) -> Vec<(T::CollectionId, T::ItemId)> {
+		Account::<T>::iter_prefix(who).map(|(key, ())| key).collect()
+	}
+
+	fn ensure_owner(collection: T::CollectionId, who: &T::AccountId) -> dispatch::DispatchResult {
+		let details = Collection::<T>::get(collection).ok_or(Error::<T>::UnknownCollection)?;
+		ensure!(&details.owner == who, Error::<T>::NoPermission);
+		Ok(())
+	}
+
+	/// Whether `who` is the owner or an admin of the collection.
+	fn can_manage(
+		collection: T::CollectionId,
+		details: &CollectionDetails<T::AccountId, DepositBalanceOf<T>>,
+		who: &T::AccountId,
+	) -> bool {
+		&details.owner == who || Admins::<T>::get(collection, who)
+	}
+}
+
+#[cfg(any(feature = "try-runtime", test))]
+impl<T: Trait> Module<T> {
+	/// Check that the item count of every collection is right and that the items held by each
+	/// account are the items it owns. Run before and after the migrations of a runtime upgrade by
+	/// `try-runtime`.
+	pub fn check_state() -> Result<(), &'static str> {
+		for (collection, details) in Collection::<T>::iter() {
+			let items = Item::<T>::iter_prefix(collection).count() as u32;
+			ensure!(details.items == items, "The item count of a collection is wrong");
+		}
+		for (collection, item, details) in Item::<T>::iter() {
+			ensure!(
+				Collection::<T>::contains_key(collection),
+				"An item is of an unknown collection",
+			);
+			ensure!(
+				Account::<T>::contains_key(&details.owner, (collection, item)),
+				"An item is missing from the items of its owner",
+			);
+		}
+		for (who, (collection, item), ()) in Account::<T>::iter() {
+			let owner = Item::<T>::get(collection, item).map(|details| details.owner);
+			ensure!(owner.as_ref() == Some(&who), "An account holds an item it does not own");
+		}
+		Ok(())
+	}
+}
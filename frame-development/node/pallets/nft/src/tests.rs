@@ -0,0 +1,158 @@
+use crate::{Error, ItemMetadata, RawEvent, mock::*};
+use frame_support::{assert_ok, assert_noop};
+use sp_runtime::DispatchError;
+
+fn last_event() -> TestEvent {
+	System::events().pop().expect("an event was deposited").event
+}
+
+fn owner(collection: u32, item: u32) -> u64 {
+	Nft::item(collection, item).expect("the item exists").owner
+}
+
+#[test]
+fn creating_a_collection_reserves_a_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::create(Origin::signed(1), 0));
+		assert_eq!(Balances::reserved_balance(1), 100);
+		assert_eq!(Nft::collection(0).unwrap().owner, 1);
+		assert_eq!(last_event(), TestEvent::nft(RawEvent::Created(0, 1)));
+
+		assert_noop!(Nft::create(Origin::signed(2), 0), Error::<Test>::InUse);
+	});
+}
+
+#[test]
+fn owner_and_admins_mint_items() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::create(Origin::signed(1), 0));
+		assert_noop!(Nft::mint(Origin::signed(2), 0, 0, 3), Error::<Test>::NoPermission);
+		assert_noop!(Nft::mint(Origin::signed(1), 1, 0, 3), Error::<Test>::UnknownCollection);
+
+		assert_ok!(Nft::mint(Origin::signed(1), 0, 0, 3));
+		assert_eq!(owner(0, 0), 3);
+		assert_eq!(last_event(), TestEvent::nft(RawEvent::Issued(0, 0, 3)));
+		assert_noop!(Nft::mint(Origin::signed(1), 0, 0, 2), Error::<Test>::AlreadyExists);
+
+		assert_noop!(Nft::add_admin(Origin::signed(2), 0, 2), Error::<Test>::NoPermission);
+		assert_ok!(Nft::add_admin(Origin::signed(1), 0, 2));
+		assert_ok!(Nft::mint(Origin::signed(2), 0, 1, 3));
+		assert_eq!(Nft::collection(0).unwrap().items, 2);
+
+		assert_ok!(Nft::remove_admin(Origin::signed(1), 0, 2));
+		assert_noop!(Nft::mint(Origin::signed(2), 0, 2, 3), Error::<Test>::NoPermission);
+	});
+}
+
+#[test]
+fn only_the_item_owner_transfers_it() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::create(Origin::signed(1), 0));
+		assert_ok!(Nft::mint(Origin::signed(1), 0, 0, 2));
+
+		// Not even the collection owner can move an item it does not hold.
+		assert_noop!(Nft::transfer(Origin::signed(1), 0, 0, 3), Error::<Test>::NoPermission);
+		assert_noop!(Nft::transfer(Origin::signed(2), 0, 1, 3), Error::<Test>::UnknownItem);
+
+		assert_ok!(Nft::transfer(Origin::signed(2), 0, 0, 3));
+		assert_eq!(owner(0, 0), 3);
+		assert_eq!(last_event(), TestEvent::nft(RawEvent::Transferred(0, 0, 2, 3)));
+		assert!(Nft::owned(&2).is_empty());
+		assert_eq!(Nft::owned(&3), vec![(0, 0)]);
+	});
+}
+
+#[test]
+fn owned_lists_the_items_of_every_collection() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::create(Origin::signed(1), 0));
+		assert_ok!(Nft::create(Origin::signed(2), 1));
+		assert_ok!(Nft::mint(Origin::signed(1), 0, 0, 3));
+		assert_ok!(Nft::mint(Origin::signed(1), 0, 1, 3));
+		assert_ok!(Nft::mint(Origin::signed(2), 1, 0, 3));
+
+		let mut owned = Nft::owned(&3);
+		owned.sort();
+		assert_eq!(owned, vec![(0, 0), (0, 1), (1, 0)]);
+	});
+}
+
+#[test]
+fn burning_returns_the_metadata_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::create(Origin::signed(1), 0));
+		assert_ok!(Nft::mint(Origin::signed(1), 0, 0, 2));
+		assert_ok!(Nft::set_metadata(Origin::signed(1), 0, 0, b"badge".to_vec()));
+		assert_eq!(Balances::reserved_balance(1), 100 + 10 + 5);
+
+		assert_noop!(Nft::burn(Origin::signed(1), 0, 0), Error::<Test>::NoPermission);
+		assert_ok!(Nft::burn(Origin::signed(2), 0, 0));
+		assert_eq!(last_event(), TestEvent::nft(RawEvent::Burned(0, 0, 2)));
+
+		assert_eq!(Balances::reserved_balance(1), 100);
+		assert!(Nft::item(0, 0).is_none());
+		assert_eq!(Nft::metadata(0, 0), Default::default());
+		assert_eq!(Nft::collection(0).unwrap().items, 0);
+		assert!(Nft::owned(&2).is_empty());
+	});
+}
+
+#[test]
+fn metadata_deposit_is_reserved_from_the_collection_owner() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::create(Origin::signed(1), 0));
+		assert_ok!(Nft::add_admin(Origin::signed(1), 0, 2));
+		assert_noop!(
+			Nft::set_metadata(Origin::signed(2), 0, 0, b"badge".to_vec()),
+			Error::<Test>::UnknownItem
+		);
+		assert_ok!(Nft::mint(Origin::signed(2), 0, 0, 3));
+
+		assert_noop!(
+			Nft::set_metadata(Origin::signed(3), 0, 0, b"badge".to_vec()),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			Nft::set_metadata(Origin::signed(2), 0, 0, vec![0; 17]),
+			Error::<Test>::BadMetadata
+		);
+
+		assert_ok!(Nft::set_metadata(Origin::signed(2), 0, 0, b"first badge".to_vec()));
+		assert_eq!(Nft::metadata(0, 0), ItemMetadata { deposit: 21, data: b"first badge".to_vec() });
+		assert_eq!(Balances::reserved_balance(1), 100 + 21);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(
+			last_event(),
+			TestEvent::nft(RawEvent::MetadataSet(0, 0, b"first badge".to_vec())),
+		);
+
+		assert_ok!(Nft::set_metadata(Origin::signed(2), 0, 0, b"badge".to_vec()));
+		assert_eq!(Balances::reserved_balance(1), 100 + 15);
+
+		assert_ok!(Nft::clear_metadata(Origin::signed(1), 0, 0));
+		assert_eq!(Balances::reserved_balance(1), 100);
+		assert_eq!(last_event(), TestEvent::nft(RawEvent::MetadataCleared(0, 0)));
+	});
+}
+
+#[test]
+fn signed_origin_is_required() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Nft::create(Origin::root(), 0), DispatchError::BadOrigin);
+		assert_noop!(Nft::transfer(Origin::none(), 0, 0, 3), DispatchError::BadOrigin);
+	});
+}
+
+#[test]
+fn check_state_finds_an_item_missing_from_its_owner() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::create(Origin::signed(1), 0));
+		assert_ok!(Nft::mint(Origin::signed(1), 0, 0, 3));
+		assert_ok!(Nft::mint(Origin::signed(1), 0, 1, 3));
+		assert_ok!(Nft::transfer(Origin::signed(3), 0, 0, 2));
+		assert_ok!(Nft::check_state());
+
+		crate::Account::<Test>::remove(2, (0, 0));
+		assert_eq!(Nft::check_state(), Err("An item is missing from the items of its owner"));
+	});
+}
@@ -0,0 +1,159 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Emergency pause of the runtime calls.
+///
+/// The pause origin can pause and resume whole pallets, or individual calls, by the names they
+/// have in `construct_runtime!`; names the runtime does not know are rejected. Paused calls are
+/// rejected by `PausedCallFilter`, which the runtime plugs into
+/// `frame_system::Trait::BaseCallFilter`. Pallets listed in `UnpausablePallets` can never be
+/// paused.
+
+use sp_std::prelude::*;
+use frame_support::{
+	decl_module, decl_storage, decl_event, decl_error, ensure, dispatch,
+	traits::{EnsureOrigin, Filter, Get, GetCallMetadata},
+};
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+/// Configure the pallet by specifying the parameters and types on which it depends.
+pub trait Trait: frame_system::Trait {
+	/// Because this pallet emits events, it depends on the runtime's definition of an event.
+	type Event: From<Event> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The origin allowed to pause and resume calls.
+	type PauseOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The names of the pallets whose calls are always allowed.
+	type UnpausablePallets: Get<Vec<Vec<u8>>>;
+
+	/// The runtime call, which knows the names of the pallets and of their calls.
+	type Call: GetCallMetadata;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Pause {
+		/// The pallets whose calls are all paused.
+		PausedPallets get(fn pallet_paused): map hasher(blake2_128_concat) Vec<u8> => bool;
+		/// The individually paused calls, by pallet and call name.
+		PausedCalls get(fn call_paused):
+			double_map hasher(blake2_128_concat) Vec<u8>, hasher(blake2_128_concat) Vec<u8> => bool;
+	}
+}
+
+decl_event!(
+	pub enum Event {
+		/// All the calls of a pallet were paused. [pallet]
+		PalletPaused(Vec<u8>),
+		/// The calls of a pallet were resumed. [pallet]
+		PalletResumed(Vec<u8>),
+		/// A call was paused. [pallet, call]
+		CallPaused(Vec<u8>, Vec<u8>),
+		/// A call was resumed. [pallet, call]
+		CallResumed(Vec<u8>, Vec<u8>),
+	}
+);
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The calls of this pallet can never be paused.
+		Unpausable,
+		/// The pallet or call is already paused.
+		AlreadyPaused,
+		/// The pallet or call is not paused.
+		NotPaused,
+		/// The runtime has no pallet of that name with calls.
+		UnknownPallet,
+		/// The pallet has no call of that name.
+		UnknownCall,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// Pause all the calls of a pallet.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+		pub fn pause_pallet(origin, pallet: Vec<u8>) -> dispatch::DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			ensure!(!T::UnpausablePallets::get().contains(&pallet), Error::<T>::Unpausable);
+			Self::ensure_known(&pallet, None)?;
+			ensure!(!PausedPallets::contains_key(&pallet), Error::<T>::AlreadyPaused);
+
+			PausedPallets::insert(&pallet, true);
+			Self::deposit_event(Event::PalletPaused(pallet));
+			Ok(())
+		}
+
+		/// Resume the calls of a paused pallet. Calls paused individually stay paused.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+		pub fn resume_pallet(origin, pallet: Vec<u8>) -> dispatch::DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			ensure!(PausedPallets::contains_key(&pallet), Error::<T>::NotPaused);
+
+			PausedPallets::remove(&pallet);
+			Self::deposit_event(Event::PalletResumed(pallet));
+			Ok(())
+		}
+
+		/// Pause a single call of a pallet.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+		pub fn pause_call(origin, pallet: Vec<u8>, call: Vec<u8>) -> dispatch::DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			ensure!(!T::UnpausablePallets::get().contains(&pallet), Error::<T>::Unpausable);
+			Self::ensure_known(&pallet, Some(&call))?;
+			ensure!(!PausedCalls::contains_key(&pallet, &call), Error::<T>::AlreadyPaused);
+
+			PausedCalls::insert(&pallet, &call, true);
+			Self::deposit_event(Event::CallPaused(pallet, call));
+			Ok(())
+		}
+
+		/// Resume a paused call. The call stays rejected while its whole pallet is paused.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+		pub fn resume_call(origin, pallet: Vec<u8>, call: Vec<u8>) -> dispatch::DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			ensure!(PausedCalls::contains_key(&pallet, &call), Error::<T>::NotPaused);
+
+			PausedCalls::remove(&pallet, &call);
+			Self::deposit_event(Event::CallResumed(pallet, call));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Ensure the runtime has a pallet named `pallet` and, if given, a call of it named `call`.
+	fn ensure_known(pallet: &[u8], call: Option<&[u8]>) -> dispatch::DispatchResult {
+		let pallet = <T as Trait>::Call::get_module_names().iter()
+			.find(|name| name.as_bytes() == pallet)
+			.ok_or(Error::<T>::UnknownPallet)?;
+		if let Some(call) = call {
+			let calls = <T as Trait>::Call::get_call_names(pallet);
+			ensure!(calls.iter().any(|name| name.as_bytes() == call), Error::<T>::UnknownCall);
+		}
+		Ok(())
+	}
+
+	/// Whether the given call is paused, either on its own or with its whole pallet.
+	pub fn is_paused(pallet: &[u8], call: &[u8]) -> bool {
+		PausedPallets::contains_key(pallet) || PausedCalls::contains_key(pallet, call)
+	}
+}
+
+/// Rejects the paused calls. Meant to be used in `frame_system::Trait::BaseCallFilter`.
+pub struct PausedCallFilter<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Trait, Call: GetCallMetadata> Filter<Call> for PausedCallFilter<T> {
+	fn filter(call: &Call) -> bool {
+		let metadata = call.get_call_metadata();
+		!Module::<T>::is_paused(metadata.pallet_name.as_bytes(), metadata.function_name.as_bytes())
+	}
+}
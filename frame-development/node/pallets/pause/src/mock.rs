@@ -0,0 +1,110 @@
+use crate::{Module, Trait};
+use sp_core::H256;
+use frame_support::{
+	impl_outer_origin, impl_outer_event, parameter_types, weights::Weight,
+	traits::{CallMetadata, GetCallMetadata},
+};
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
+};
+use frame_system as system;
+
+impl_outer_origin! {
+	pub enum Origin for Test {}
+}
+
+mod pause {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum TestEvent for Test {
+		system<T>,
+		pause,
+	}
+}
+
+// Configure a mock runtime to test the pallet.
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+}
+
+impl system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Call = ();
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = TestEvent;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type PalletInfo = ();
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+
+parameter_types! {
+	pub UnpausablePallets: Vec<Vec<u8>> = vec![b"System".to_vec(), b"Pause".to_vec()];
+}
+
+impl Trait for Test {
+	type Event = TestEvent;
+	type PauseOrigin = system::EnsureRoot<u64>;
+	type UnpausablePallets = UnpausablePallets;
+	type Call = MockCall;
+}
+
+pub type System = system::Module<Test>;
+pub type Pause = Module<Test>;
+
+/// A runtime call, as far as the filter is concerned.
+pub struct MockCall(pub &'static str, pub &'static str);
+
+impl GetCallMetadata for MockCall {
+	fn get_module_names() -> &'static [&'static str] {
+		&["System", "Balances", "Staking", "Pause"]
+	}
+
+	fn get_call_names(module: &str) -> &'static [&'static str] {
+		match module {
+			"System" => &["remark"],
+			"Balances" => &["transfer", "transfer_keep_alive"],
+			"Staking" => &["bond"],
+			"Pause" => &["pause_pallet", "resume_pallet", "pause_call", "resume_call"],
+			_ => &[],
+		}
+	}
+
+	fn get_call_metadata(&self) -> CallMetadata {
+		CallMetadata { pallet_name: self.0, function_name: self.1 }
+	}
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext: sp_io::TestExternalities =
+		system::GenesisConfig::default().build_storage::<Test>().unwrap().into();
+	// Events are not recorded in the genesis block.
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
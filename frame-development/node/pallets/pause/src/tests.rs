@@ -0,0 +1,131 @@
+use crate::{Error, Event, PausedCallFilter, mock::*};
+use frame_support::{assert_ok, assert_noop, traits::Filter};
+use sp_runtime::DispatchError;
+
+fn allowed(pallet: &'static str, call: &'static str) -> bool {
+	PausedCallFilter::<Test>::filter(&MockCall(pallet, call))
+}
+
+fn last_event() -> TestEvent {
+	System::events().pop().expect("an event was deposited").event
+}
+
+#[test]
+fn paused_pallet_rejects_all_its_calls() {
+	new_test_ext().execute_with(|| {
+		assert!(allowed("Balances", "transfer"));
+
+		assert_ok!(Pause::pause_pallet(Origin::root(), b"Balances".to_vec()));
+		assert!(!allowed("Balances", "transfer"));
+		assert!(!allowed("Balances", "transfer_keep_alive"));
+		assert!(allowed("Staking", "bond"));
+
+		assert_ok!(Pause::resume_pallet(Origin::root(), b"Balances".to_vec()));
+		assert!(allowed("Balances", "transfer"));
+	});
+}
+
+#[test]
+fn paused_call_rejects_only_that_call() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pause::pause_call(Origin::root(), b"Balances".to_vec(), b"transfer".to_vec()));
+		assert!(!allowed("Balances", "transfer"));
+		assert!(allowed("Balances", "transfer_keep_alive"));
+
+		assert_ok!(Pause::resume_call(Origin::root(), b"Balances".to_vec(), b"transfer".to_vec()));
+		assert!(allowed("Balances", "transfer"));
+	});
+}
+
+#[test]
+fn resumed_call_stays_rejected_while_its_pallet_is_paused() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pause::pause_call(Origin::root(), b"Balances".to_vec(), b"transfer".to_vec()));
+		assert_ok!(Pause::pause_pallet(Origin::root(), b"Balances".to_vec()));
+		assert_ok!(Pause::resume_call(Origin::root(), b"Balances".to_vec(), b"transfer".to_vec()));
+
+		assert!(!allowed("Balances", "transfer"));
+	});
+}
+
+#[test]
+fn only_the_pause_origin_can_pause() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Pause::pause_pallet(Origin::signed(1), b"Balances".to_vec()),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Pause::pause_call(Origin::signed(1), b"Balances".to_vec(), b"transfer".to_vec()),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn unpausable_pallets_cannot_be_paused() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Pause::pause_pallet(Origin::root(), b"System".to_vec()),
+			Error::<Test>::Unpausable
+		);
+		assert_noop!(
+			Pause::pause_call(Origin::root(), b"Pause".to_vec(), b"resume_pallet".to_vec()),
+			Error::<Test>::Unpausable
+		);
+	});
+}
+
+#[test]
+fn pausing_twice_or_resuming_what_is_not_paused_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Pause::resume_pallet(Origin::root(), b"Balances".to_vec()),
+			Error::<Test>::NotPaused
+		);
+
+		assert_ok!(Pause::pause_pallet(Origin::root(), b"Balances".to_vec()));
+		assert_noop!(
+			Pause::pause_pallet(Origin::root(), b"Balances".to_vec()),
+			Error::<Test>::AlreadyPaused
+		);
+	});
+}
+
+#[test]
+fn unknown_pallets_and_calls_cannot_be_paused() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Pause::pause_pallet(Origin::root(), b"Balance".to_vec()),
+			Error::<Test>::UnknownPallet
+		);
+		assert_noop!(
+			Pause::pause_call(Origin::root(), b"Balance".to_vec(), b"transfer".to_vec()),
+			Error::<Test>::UnknownPallet
+		);
+		assert_noop!(
+			Pause::pause_call(Origin::root(), b"Balances".to_vec(), b"Transfer".to_vec()),
+			Error::<Test>::UnknownCall
+		);
+	});
+}
+
+#[test]
+fn every_state_change_emits_an_event() {
+	new_test_ext().execute_with(|| {
+		let pallet = b"Balances".to_vec();
+		let call = b"transfer".to_vec();
+
+		assert_ok!(Pause::pause_pallet(Origin::root(), pallet.clone()));
+		assert_eq!(last_event(), TestEvent::pause(Event::PalletPaused(pallet.clone())));
+
+		assert_ok!(Pause::resume_pallet(Origin::root(), pallet.clone()));
+		assert_eq!(last_event(), TestEvent::pause(Event::PalletResumed(pallet.clone())));
+
+		assert_ok!(Pause::pause_call(Origin::root(), pallet.clone(), call.clone()));
+		assert_eq!(last_event(), TestEvent::pause(Event::CallPaused(pallet.clone(), call.clone())));
+
+		assert_ok!(Pause::resume_call(Origin::root(), pallet.clone(), call.clone()));
+		assert_eq!(last_event(), TestEvent::pause(Event::CallResumed(pallet, call)));
+	});
+}
@@ -0,0 +1,188 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// On-chain randomness from a commit-reveal scheme among the authorities.
+///
+/// Time is split into rounds of `RoundLength` blocks. During a round, each participant commits
+/// to a secret of its choosing by submitting the hash of its account and the secret, and reveals
+/// the secret it committed to in the previous round. The revealed secrets are XORed together, so
+/// that neither their order nor the block author can influence the result, and mixed into the
+/// seed when the next round starts.
+///
+/// A participant cannot choose its secret once it knows the others', so the seed cannot be
+/// biased beyond the choice, made by the last participants to reveal, of withholding their
+/// secrets. Withheld secrets are reported with `NotRevealed` events.
+///
+/// The parent hash of the first block of a round is mixed into its seed, and the parent hash of
+/// every block into the random values derived from the seed, so that values differ from block
+/// to block and the seed moves on even in rounds nobody revealed a secret in. The parent hash is
+/// known to the author of the block and can be biased by it, so the seed is only unpredictable
+/// once at least one honest participant revealed a secret. Nothing commits or reveals on behalf
+/// of the participants: each of them submits its own `commit` and `reveal` extrinsics.
+
+use frame_support::{
+	decl_module, decl_storage, decl_event, decl_error, ensure, dispatch,
+	traits::{Contains, Get, Randomness},
+	weights::Weight,
+};
+use frame_system::ensure_signed;
+use sp_runtime::traits::{Hash, Zero};
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+/// The index of a round of the beacon.
+pub type RoundIndex = u32;
+
+/// A secret revealed by a participant.
+pub type Secret = [u8; 32];
+
+/// Configure the pallet by specifying the parameters and types on which it depends.
+pub trait Trait: frame_system::Trait {
+	/// Because this pallet emits events, it depends on the runtime's definition of an event.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The accounts allowed to commit and reveal secrets.
+	type Participants: Contains<Self::AccountId>;
+
+	/// The number of blocks in a round.
+	type RoundLength: Get<Self::BlockNumber>;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as RandomnessBeacon {
+		/// The current round.
+		CurrentRound get(fn current_round): RoundIndex;
+		/// The seed produced at the start of the current round.
+		Seed get(fn seed): T::Hash;
+		/// The XOR of the secrets revealed during the current round.
+		Accumulator: Secret;
+		/// The commitments made in each round, by participant.
+		Commitments get(fn commitment):
+			double_map hasher(twox_64_concat) RoundIndex, hasher(blake2_128_concat) T::AccountId
+			=> Option<T::Hash>;
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where
+		AccountId = <T as frame_system::Trait>::AccountId,
+		Hash = <T as frame_system::Trait>::Hash,
+	{
+		/// A participant committed to a secret. [round, who]
+		Committed(RoundIndex, AccountId),
+		/// A participant revealed the secret it committed to in the previous round. [round, who]
+		Revealed(RoundIndex, AccountId),
+		/// A participant did not reveal the secret it committed to. [round of the commitment, who]
+		NotRevealed(RoundIndex, AccountId),
+		/// A round started with a new seed. [round, seed]
+		NewSeed(RoundIndex, Hash),
+	}
+);
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The signer is not allowed to take part in the beacon.
+		NotParticipant,
+		/// The signer already committed to a secret in this round.
+		AlreadyCommitted,
+		/// The signer did not commit to a secret in the previous round, or already revealed it.
+		NoCommitment,
+		/// The secret does not match the commitment.
+		BadReveal,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			if (n % T::RoundLength::get()).is_zero() {
+				Self::start_round()
+			} else {
+				0
+			}
+		}
+
+		/// Commit to a secret to reveal in the next round. The commitment is the hash of the
+		/// signer's account and the secret.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(2, 1)]
+		pub fn commit(origin, commitment: T::Hash) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(T::Participants::contains(&who), Error::<T>::NotParticipant);
+
+			let round = CurrentRound::get();
+			ensure!(!Commitments::<T>::contains_key(round, &who), Error::<T>::AlreadyCommitted);
+
+			Commitments::<T>::insert(round, &who, commitment);
+			Self::deposit_event(RawEvent::Committed(round, who));
+			Ok(())
+		}
+
+		/// Reveal the secret committed to in the previous round.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(3, 2)]
+		pub fn reveal(origin, secret: Secret) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let round = CurrentRound::get();
+			let committed = round.checked_sub(1)
+				.and_then(|previous| Commitments::<T>::get(previous, &who))
+				.ok_or(Error::<T>::NoCommitment)?;
+			ensure!(committed == Self::commitment_of(&who, &secret), Error::<T>::BadReveal);
+
+			Commitments::<T>::remove(round - 1, &who);
+			Accumulator::mutate(|accumulator| {
+				accumulator.iter_mut().zip(secret.iter()).for_each(|(a, s)| *a ^= s);
+			});
+			Self::deposit_event(RawEvent::Revealed(round, who));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// The commitment of `who` to `secret`.
+	pub fn commitment_of(who: &T::AccountId, secret: &Secret) -> T::Hash {
+		T::Hashing::hash_of(&(who, secret))
+	}
+
+	/// Mix the secrets revealed during the ending round into the seed, and drop the commitments
+	/// that should have been revealed in it.
+	fn start_round() -> Weight {
+		let ended = CurrentRound::get();
+		let mut stale = 0;
+		if let Some(previous) = ended.checked_sub(1) {
+			for (who, _) in Commitments::<T>::iter_prefix(previous) {
+				Self::deposit_event(RawEvent::NotRevealed(previous, who));
+				stale += 1;
+			}
+			Commitments::<T>::remove_prefix(previous);
+		}
+
+		let seed = T::Hashing::hash_of(&(
+			Seed::<T>::get(),
+			Accumulator::take(),
+			<frame_system::Module<T>>::parent_hash(),
+		));
+		let round = ended.saturating_add(1);
+		Seed::<T>::put(seed);
+		CurrentRound::put(round);
+		Self::deposit_event(RawEvent::NewSeed(round, seed));
+
+		T::DbWeight::get().reads_writes(4 + stale, 4 + stale)
+	}
+}
+
+impl<T: Trait> Randomness<T::Hash> for Module<T> {
+	/// A random value for `subject`, derived from the seed of the current round and the parent
+	/// hash of the current block. Different subjects get independent values, and the value for a
+	/// given subject changes from block to block.
+	fn random(subject: &[u8]) -> T::Hash {
+		T::Hashing::hash_of(&(Seed::<T>::get(), <frame_system::Module<T>>::parent_hash(), subject))
+	}
+}
@@ -0,0 +1,110 @@
+use crate::{Module, Trait};
+use sp_core::H256;
+use frame_support::{
+	impl_outer_origin, impl_outer_event, parameter_types, weights::Weight,
+	traits::{Contains, OnInitialize},
+};
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
+};
+use frame_system as system;
+
+impl_outer_origin! {
+	pub enum Origin for Test {}
+}
+
+mod beacon {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum TestEvent for Test {
+		system<T>,
+		beacon<T>,
+	}
+}
+
+// Configure a mock runtime to test the pallet.
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+}
+
+impl system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Call = ();
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = TestEvent;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type PalletInfo = ();
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+
+/// Accounts 1, 2 and 3 take part in the beacon.
+pub struct Participants;
+
+impl Contains<u64> for Participants {
+	fn sorted_members() -> Vec<u64> {
+		vec![1, 2, 3]
+	}
+}
+
+parameter_types! {
+	pub const RoundLength: u64 = 10;
+}
+
+impl Trait for Test {
+	type Event = TestEvent;
+	type Participants = Participants;
+	type RoundLength = RoundLength;
+}
+
+pub type System = system::Module<Test>;
+pub type Beacon = Module<Test>;
+
+/// The hash of the parent of block `n`, distinct for every block.
+pub fn parent_hash(n: u64) -> H256 {
+	H256::from_low_u64_be(n - 1)
+}
+
+/// Initialize blocks up to `n`, starting new rounds on the way.
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		let number = System::block_number() + 1;
+		System::set_block_number(number);
+		System::set_parent_hash(parent_hash(number));
+		Beacon::on_initialize(number);
+	}
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext: sp_io::TestExternalities =
+		system::GenesisConfig::default().build_storage::<Test>().unwrap().into();
+	// Events are not recorded in the genesis block.
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,157 @@
+use crate::{Error, RawEvent, Secret, mock::*};
+use frame_support::{assert_ok, assert_noop, traits::Randomness};
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+fn commit(who: u64, secret: &Secret) {
+	assert_ok!(Beacon::commit(Origin::signed(who), Beacon::commitment_of(&who, secret)));
+}
+
+fn events() -> Vec<TestEvent> {
+	System::events().into_iter().map(|r| r.event).collect()
+}
+
+#[test]
+fn rounds_start_every_round_length_blocks() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Beacon::current_round(), 0);
+		run_to_block(9);
+		assert_eq!(Beacon::current_round(), 0);
+		run_to_block(10);
+		assert_eq!(Beacon::current_round(), 1);
+		assert!(events().contains(&TestEvent::beacon(RawEvent::NewSeed(1, Beacon::seed()))));
+		run_to_block(30);
+		assert_eq!(Beacon::current_round(), 3);
+	});
+}
+
+#[test]
+fn revealed_secrets_change_the_seed() {
+	let seed_after = |reveal: bool| new_test_ext().execute_with(|| {
+		commit(1, &[1; 32]);
+		run_to_block(10);
+		if reveal {
+			assert_ok!(Beacon::reveal(Origin::signed(1), [1; 32]));
+			assert_eq!(
+				events().pop().unwrap(),
+				TestEvent::beacon(RawEvent::Revealed(1, 1)),
+			);
+		}
+		run_to_block(20);
+		Beacon::seed()
+	});
+
+	assert_ne!(seed_after(true), seed_after(false));
+}
+
+#[test]
+fn seed_is_unpredictable_only_after_a_reveal() {
+	let seed_after = |reveal: bool| new_test_ext().execute_with(|| {
+		commit(1, &[1; 32]);
+		run_to_block(10);
+		if reveal {
+			assert_ok!(Beacon::reveal(Origin::signed(1), [1; 32]));
+		}
+		// Without secrets, the next seed only depends on what is known before the round starts.
+		let predicted = BlakeTwo256::hash_of(&(Beacon::seed(), [0u8; 32], parent_hash(20)));
+		run_to_block(20);
+		(Beacon::seed(), predicted)
+	});
+
+	let (withheld, predicted) = seed_after(false);
+	assert_eq!(withheld, predicted);
+	let (revealed, predicted) = seed_after(true);
+	assert_ne!(revealed, predicted);
+}
+
+#[test]
+fn seed_moves_on_without_reveals() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+		let first = Beacon::seed();
+		assert_ne!(first, Default::default());
+		run_to_block(20);
+		assert_ne!(Beacon::seed(), first);
+	});
+}
+
+#[test]
+fn reveal_order_does_not_matter() {
+	let seed_after = |order: [u64; 2]| new_test_ext().execute_with(|| {
+		commit(1, &[1; 32]);
+		commit(2, &[2; 32]);
+		run_to_block(10);
+		for who in order.iter() {
+			assert_ok!(Beacon::reveal(Origin::signed(*who), [*who as u8; 32]));
+		}
+		run_to_block(20);
+		Beacon::seed()
+	});
+
+	assert_eq!(seed_after([1, 2]), seed_after([2, 1]));
+}
+
+#[test]
+fn only_participants_commit_once_per_round() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Beacon::commit(Origin::signed(4), Beacon::commitment_of(&4, &[4; 32])),
+			Error::<Test>::NotParticipant
+		);
+
+		commit(1, &[1; 32]);
+		assert_noop!(
+			Beacon::commit(Origin::signed(1), Beacon::commitment_of(&1, &[2; 32])),
+			Error::<Test>::AlreadyCommitted
+		);
+
+		// A participant commits in every round.
+		run_to_block(10);
+		commit(1, &[2; 32]);
+	});
+}
+
+#[test]
+fn secrets_must_match_the_commitment_of_the_previous_round() {
+	new_test_ext().execute_with(|| {
+		commit(1, &[1; 32]);
+		// Secrets cannot be revealed in the round they were committed to.
+		assert_noop!(Beacon::reveal(Origin::signed(1), [1; 32]), Error::<Test>::NoCommitment);
+
+		run_to_block(10);
+		assert_noop!(Beacon::reveal(Origin::signed(1), [2; 32]), Error::<Test>::BadReveal);
+		// Nor can a participant claim the commitment of another.
+		assert_noop!(Beacon::reveal(Origin::signed(2), [1; 32]), Error::<Test>::NoCommitment);
+
+		assert_ok!(Beacon::reveal(Origin::signed(1), [1; 32]));
+		assert_noop!(Beacon::reveal(Origin::signed(1), [1; 32]), Error::<Test>::NoCommitment);
+	});
+}
+
+#[test]
+fn withheld_secrets_are_reported() {
+	new_test_ext().execute_with(|| {
+		commit(1, &[1; 32]);
+		commit(2, &[2; 32]);
+		run_to_block(10);
+		assert_ok!(Beacon::reveal(Origin::signed(1), [1; 32]));
+
+		run_to_block(20);
+		assert!(events().contains(&TestEvent::beacon(RawEvent::NotRevealed(0, 2))));
+		assert!(!events().contains(&TestEvent::beacon(RawEvent::NotRevealed(0, 1))));
+		assert_eq!(Beacon::commitment(0, 2), None);
+	});
+}
+
+#[test]
+fn random_values_depend_on_the_subject_and_the_block() {
+	new_test_ext().execute_with(|| {
+		run_to_block(2);
+		let first = Beacon::random(b"first");
+		assert_ne!(first, Beacon::random(b"second"));
+		assert_eq!(first, Beacon::random(b"first"));
+
+		// Within a round, values change with the parent hash.
+		run_to_block(3);
+		assert_ne!(first, Beacon::random(b"first"));
+	});
+}
@@ -0,0 +1,81 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// One-shot hand over of the chain from the sudo key to on-chain governance.
+///
+/// Once governance is able to take decisions on its own, root (i.e. sudo itself, or a
+/// referendum) calls `retire_sudo`. This removes the sudo key from storage and flags sudo as
+/// retired, so that the runtime can reject any further sudo call. A later runtime upgrade can
+/// then drop `pallet_sudo` from `construct_runtime!` altogether.
+
+use sp_std::prelude::*;
+use frame_support::{decl_module, decl_storage, decl_event, decl_error, ensure, dispatch, traits::Get};
+use frame_system::ensure_root;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+/// Configure the pallet by specifying the parameters and types on which it depends.
+pub trait Trait: frame_system::Trait {
+	/// Because this pallet emits events, it depends on the runtime's definition of an event.
+	type Event: From<Event> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// Whether governance is ready to take over the control of the chain.
+	type GovernanceReady: Get<bool>;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as SudoHandover {
+		/// Whether sudo has been retired.
+		Retired get(fn retired): bool;
+	}
+}
+
+decl_event!(
+	pub enum Event {
+		/// Sudo handed the control of the chain over to governance.
+		SudoRetired,
+	}
+);
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// Sudo has already been retired.
+		AlreadyRetired,
+		/// Governance is not able to take over yet.
+		GovernanceNotReady,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// Remove the sudo key and flag sudo as retired. This function must be dispatched by
+		/// root, and can only succeed once.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 2)]
+		pub fn retire_sudo(origin) -> dispatch::DispatchResult {
+			ensure_root(origin)?;
+			ensure!(!Retired::get(), Error::<T>::AlreadyRetired);
+			ensure!(T::GovernanceReady::get(), Error::<T>::GovernanceNotReady);
+
+			// The key is the only thing `pallet_sudo` keeps in storage.
+			frame_support::storage::unhashed::kill(&Self::sudo_key_storage_key());
+			Retired::put(true);
+
+			Self::deposit_event(Event::SudoRetired);
+			Ok(())
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// The storage key of the `Key` value of `pallet_sudo`.
+	pub fn sudo_key_storage_key() -> Vec<u8> {
+		[sp_io::hashing::twox_128(b"Sudo"), sp_io::hashing::twox_128(b"Key")].concat()
+	}
+}
@@ -0,0 +1,41 @@
+use crate::{Error, mock::*};
+use frame_support::{assert_ok, assert_noop, storage::unhashed};
+use sp_runtime::DispatchError;
+
+#[test]
+fn root_retires_sudo() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(unhashed::get::<u64>(&SudoHandover::sudo_key_storage_key()), Some(SUDO_KEY));
+
+		assert_ok!(SudoHandover::retire_sudo(Origin::root()));
+
+		// The sudo key is gone and sudo is flagged as retired.
+		assert_eq!(unhashed::get::<u64>(&SudoHandover::sudo_key_storage_key()), None);
+		assert!(SudoHandover::retired());
+	});
+}
+
+#[test]
+fn only_root_can_retire_sudo() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(SudoHandover::retire_sudo(Origin::signed(SUDO_KEY)), DispatchError::BadOrigin);
+	});
+}
+
+#[test]
+fn sudo_is_retired_only_once() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SudoHandover::retire_sudo(Origin::root()));
+		assert_noop!(SudoHandover::retire_sudo(Origin::root()), Error::<Test>::AlreadyRetired);
+	});
+}
+
+#[test]
+fn sudo_is_kept_until_governance_is_ready() {
+	new_test_ext().execute_with(|| {
+		GOVERNANCE_READY.with(|v| *v.borrow_mut() = false);
+
+		assert_noop!(SudoHandover::retire_sudo(Origin::root()), Error::<Test>::GovernanceNotReady);
+		assert_eq!(unhashed::get::<u64>(&SudoHandover::sudo_key_storage_key()), Some(SUDO_KEY));
+	});
+}
@@ -0,0 +1,69 @@
+//! Runtime APIs exposing state of the node template runtime that no upstream API covers.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+	/// The treasury funded by fees, tips, slashes and dust.
+	pub trait TreasuryApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// The account holding the treasury funds.
+		fn account_id() -> AccountId;
+		/// The funds the treasury can spend, i.e. without the existential deposit.
+		fn balance() -> Balance;
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// The vesting schedules of the accounts.
+	pub trait VestingApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// The part of the account's schedule still locked at the current block, or zero without
+		/// a schedule.
+		fn locked(who: AccountId) -> Balance;
+		/// The part of the account's schedule vested so far. The balance lock only shrinks once
+		/// the account calls `vest`.
+		fn vested(who: AccountId) -> Balance;
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// The fungible assets issued on the chain.
+	pub trait AssetsApi<AccountId, AssetId, Balance> where
+		AccountId: Codec,
+		AssetId: Codec,
+		Balance: Codec,
+	{
+		/// The balances held by the account, by asset. Assets it holds nothing of are left out.
+		fn account_balances(who: AccountId) -> Vec<(AssetId, Balance)>;
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// The non-fungible tokens issued on the chain.
+	pub trait NftApi<AccountId, CollectionId, ItemId> where
+		AccountId: Codec,
+		CollectionId: Codec,
+		ItemId: Codec,
+	{
+		/// The items held by the account, by collection and item identifier.
+		fn owned(who: AccountId) -> Vec<(CollectionId, ItemId)>;
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// Dry runs of runtime upgrades, implemented when the runtime is built with the `try-runtime`
+	/// feature.
+	pub trait TryRuntime {
+		/// Run the migrations of the upgrade to this runtime, checking the state of the pallets
+		/// before and after them. Returns the weight the migrations used, or the message of the
+		/// first failing check.
+		fn on_runtime_upgrade() -> Result<u64, Vec<u8>>;
+	}
+}
@@ -0,0 +1,17 @@
+//! Genesis values shared by the chain specs of the node and the tests of the runtime, so that the
+//! tests run against the same balances and keys as the development chains.
+
+use sp_core::{Pair, Public};
+use crate::Balance;
+
+/// Initial balance of every endowed account.
+pub const ENDOWMENT: Balance = 1 << 60;
+/// Amount bonded by each genesis validator out of its stash.
+pub const STASH: Balance = ENDOWMENT / 1000;
+
+/// Generate a crypto pair from seed.
+pub fn get_from_seed<TPublic: Public>(seed: &str) -> <TPublic::Pair as Pair>::Public {
+	TPublic::Pair::from_string(&format!("//{}", seed), None)
+		.expect("static values are valid; qed")
+		.public()
+}
@@ -0,0 +1,136 @@
+//! Some configurable implementations as associated type for the runtime.
+
+use sp_std::prelude::*;
+use sp_runtime::{Perbill, traits::Convert};
+use frame_support::{
+	traits::{Contains, Currency, Filter, Get, Imbalance, OnUnbalanced},
+	weights::{
+		ExtrinsicBaseWeight, WeightToFeeCoefficient, WeightToFeeCoefficients, WeightToFeePolynomial,
+	},
+};
+use smallvec::smallvec;
+use pallet_pause::PausedCallFilter;
+use crate::{
+	AccountId, Authorship, Balance, Balances, CENTS, Call, Council, FeesToTreasury, Runtime, Session,
+	Staking, SudoHandover, TipsToTreasury, Treasury,
+};
+
+type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
+
+/// Pays the block author.
+pub struct Author;
+
+impl OnUnbalanced<NegativeImbalance> for Author {
+	fn on_nonzero_unbalanced(amount: NegativeImbalance) {
+		Balances::resolve_creating(&Authorship::author(), amount);
+	}
+}
+
+/// Splits transaction fees and tips between the treasury and the block author, according to
+/// `FeesToTreasury` and `TipsToTreasury`.
+pub struct DealWithFees;
+
+impl OnUnbalanced<NegativeImbalance> for DealWithFees {
+	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item=NegativeImbalance>) {
+		if let Some(fees) = fees_then_tips.next() {
+			let to_treasury = FeesToTreasury::get().deconstruct() as u32;
+			let mut split = fees.ration(to_treasury, 100 - to_treasury);
+			if let Some(tips) = fees_then_tips.next() {
+				let to_treasury = TipsToTreasury::get().deconstruct() as u32;
+				tips.ration_merge_into(to_treasury, 100 - to_treasury, &mut split);
+			}
+			Treasury::on_unbalanced(split.0);
+			Author::on_unbalanced(split.1);
+		}
+	}
+}
+
+/// Struct that handles the conversion of Balance -> `u64`. This is used for staking's election
+/// calculation.
+pub struct CurrencyToVoteHandler;
+
+impl CurrencyToVoteHandler {
+	fn factor() -> Balance {
+		(Balances::total_issuance() / u64::max_value() as Balance).max(1)
+	}
+}
+
+impl Convert<Balance, u64> for CurrencyToVoteHandler {
+	fn convert(x: Balance) -> u64 {
+		(x / Self::factor()) as u64
+	}
+}
+
+impl Convert<u128, Balance> for CurrencyToVoteHandler {
+	fn convert(x: u128) -> Balance {
+		x * Self::factor()
+	}
+}
+
+/// Converts the weight of an extrinsic into a fee.
+///
+/// The extrinsic base weight costs about one cent, and the quadratic term makes heavy
+/// extrinsics increasingly expensive, so that a few of them cannot cheaply fill a block.
+pub struct WeightToFee;
+
+impl WeightToFeePolynomial for WeightToFee {
+	type Balance = Balance;
+
+	fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+		let p = CENTS;
+		let q = Balance::from(ExtrinsicBaseWeight::get());
+		smallvec![
+			WeightToFeeCoefficient {
+				degree: 1,
+				negative: false,
+				coeff_frac: Perbill::from_rational_approximation(p % q, q),
+				coeff_integer: p / q,
+			},
+			WeightToFeeCoefficient {
+				degree: 2,
+				negative: false,
+				coeff_frac: Perbill::from_parts(1),
+				coeff_integer: 0,
+			},
+		]
+	}
+}
+
+/// Governance is ready to take over from sudo once the council has been seated.
+pub struct CouncilSeated;
+
+impl Get<bool> for CouncilSeated {
+	fn get() -> bool {
+		!Council::members().is_empty()
+	}
+}
+
+/// The basic call filter of the runtime. Sudo calls are rejected once sudo has been retired, and
+/// other calls while they are paused.
+pub struct BaseFilter;
+
+impl Filter<Call> for BaseFilter {
+	fn filter(call: &Call) -> bool {
+		match call {
+			Call::Sudo(_) => !SudoHandover::retired(),
+			_ => PausedCallFilter::<Runtime>::filter(call),
+		}
+	}
+}
+
+/// The controller accounts of the active validators.
+pub struct ValidatorControllers;
+
+impl Contains<AccountId> for ValidatorControllers {
+	fn sorted_members() -> Vec<AccountId> {
+		let mut controllers: Vec<_> = Session::validators().into_iter()
+			.filter_map(Staking::bonded)
+			.collect();
+		controllers.sort();
+		controllers
+	}
+
+	fn contains(who: &AccountId) -> bool {
+		Session::validators().iter().any(|v| Staking::bonded(v).as_ref() == Some(who))
+	}
+}
@@ -2,23 +2,37 @@
 // `construct_runtime!` does a lot of recursion and requires us to increase the limit to 256.
 #![recursion_limit="256"]
 
+#[cfg(not(any(feature = "aura", feature = "babe")))]
+compile_error!("The runtime needs a block authoring pallet: enable the `aura` or `babe` feature.");
+
 // Make the WASM binary available.
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 use sp_std::prelude::*;
-use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
+use codec::{Encode, Decode};
+use sp_core::{
+	crypto::KeyTypeId, OpaqueMetadata,
+	u32_trait::{_1, _2, _3, _4, _5},
+};
 use sp_runtime::{
 	ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, MultiSignature,
-	transaction_validity::{TransactionValidity, TransactionSource},
+	curve::PiecewiseLinear, FixedPointNumber, Perquintill, RuntimeDebug,
+	transaction_validity::{TransactionValidity, TransactionSource, TransactionPriority},
 };
 use sp_runtime::traits::{
-	BlakeTwo256, Block as BlockT, IdentityLookup, Verify, IdentifyAccount, NumberFor, Saturating,
+	BlakeTwo256, Block as BlockT, Verify, IdentifyAccount, NumberFor, Saturating, OpaqueKeys,
+	StaticLookup, ConvertInto,
 };
 use sp_api::impl_runtime_apis;
+use frame_system::{EnsureRoot, EnsureOneOf};
+#[cfg(not(feature = "babe"))]
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use pallet_grandpa::{AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList};
 use pallet_grandpa::fg_primitives;
+use pallet_session::historical as pallet_session_historical;
+use pallet_transaction_payment::{Multiplier, TargetedFeeAdjustment};
+use sp_staking::SessionIndex;
 use sp_version::RuntimeVersion;
 #[cfg(feature = "std")]
 use sp_version::NativeVersion;
@@ -28,12 +42,13 @@ use sp_version::NativeVersion;
 pub use sp_runtime::BuildStorage;
 pub use pallet_timestamp::Call as TimestampCall;
 pub use pallet_balances::Call as BalancesCall;
-pub use sp_runtime::{Permill, Perbill};
+pub use pallet_staking::StakerStatus;
+pub use sp_runtime::{Permill, Perbill, Percent, ModuleId};
 pub use frame_support::{
 	construct_runtime, parameter_types, StorageValue,
-	traits::{KeyOwnerProofSystem, Randomness},
+	traits::{InstanceFilter, KeyOwnerProofSystem, LockIdentifier, Randomness},
 	weights::{
-		Weight, IdentityFee,
+		Weight,
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 	},
 };
@@ -41,6 +56,19 @@ pub use frame_support::{
 /// Import the template pallet.
 pub use pallet_template;
 
+/// Implementations of some helper traits passed into runtime modules as associated types.
+pub mod impls;
+/// Genesis values shared with the chain specs of the node.
+#[cfg(feature = "std")]
+pub mod genesis;
+use impls::{BaseFilter, CouncilSeated, CurrencyToVoteHandler, DealWithFees, WeightToFee};
+#[cfg(not(feature = "babe"))]
+use impls::ValidatorControllers;
+
+/// Dry runs of runtime upgrades, see `node_template_runtime_api::TryRuntime`.
+#[cfg(feature = "try-runtime")]
+mod try_runtime;
+
 /// An index to a block.
 pub type BlockNumber = u32;
 
@@ -58,6 +86,15 @@ pub type AccountIndex = u32;
 /// Balance of an account.
 pub type Balance = u128;
 
+/// Identifier of a fungible asset.
+pub type AssetId = u32;
+
+/// Identifier of a collection of non-fungible tokens.
+pub type CollectionId = u32;
+
+/// Identifier of a non-fungible token within its collection.
+pub type ItemId = u32;
+
 /// Index of a transaction in the chain.
 pub type Index = u32;
 
@@ -83,12 +120,21 @@ pub mod opaque {
 	/// Opaque block identifier type.
 	pub type BlockId = generic::BlockId<Block>;
 
+	#[cfg(not(feature = "babe"))]
 	impl_opaque_keys! {
 		pub struct SessionKeys {
 			pub aura: Aura,
 			pub grandpa: Grandpa,
 		}
 	}
+
+	#[cfg(feature = "babe")]
+	impl_opaque_keys! {
+		pub struct SessionKeys {
+			pub babe: Babe,
+			pub grandpa: Grandpa,
+		}
+	}
 }
 
 pub const VERSION: RuntimeVersion = RuntimeVersion {
@@ -98,7 +144,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_version: 1,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 pub const MILLISECS_PER_BLOCK: u64 = 6000;
@@ -110,6 +156,31 @@ pub const MINUTES: BlockNumber = 60_000 / (MILLISECS_PER_BLOCK as BlockNumber);
 pub const HOURS: BlockNumber = MINUTES * 60;
 pub const DAYS: BlockNumber = HOURS * 24;
 
+// Staking eras are made of sessions, which are measured in blocks as well.
+pub const SESSION_PERIOD: BlockNumber = 1 * HOURS;
+pub const SESSIONS_PER_ERA: SessionIndex = 6;
+pub const ERA_PERIOD: BlockNumber = SESSION_PERIOD * SESSIONS_PER_ERA;
+
+// BABE epochs last as long as sessions, so that each session starts with a new epoch.
+pub const EPOCH_DURATION_IN_SLOTS: u64 = SESSION_PERIOD as u64;
+/// The probability of a slot having a primary, VRF-assigned, author. The other slots fall back
+/// to a secondary author picked in turn from the authorities.
+pub const PRIMARY_PROBABILITY: (u64, u64) = (1, 4);
+
+// Balances are expressed in units of 10^12 of the smallest denomination.
+pub const UNITS: Balance = 1_000_000_000_000;
+pub const CENTS: Balance = UNITS / 100;
+pub const MILLICENTS: Balance = CENTS / 1_000;
+
+// How wallets display balances: in `UNITS`, i.e. with 12 decimals.
+pub const TOKEN_SYMBOL: &str = "UNIT";
+pub const TOKEN_DECIMALS: u8 = 12;
+
+/// The deposit reserved for keeping `items` storage items of `bytes` bytes in total.
+pub const fn deposit(items: u32, bytes: u32) -> Balance {
+	items as Balance * 15 * CENTS + (bytes as Balance) * 6 * CENTS
+}
+
 /// The version information used to identify this runtime when compiled natively.
 #[cfg(feature = "std")]
 pub fn native_version() -> NativeVersion {
@@ -129,19 +200,24 @@ parameter_types! {
 		.saturating_sub(Perbill::from_percent(10)) * MaximumBlockWeight::get();
 	pub const MaximumBlockLength: u32 = 5 * 1024 * 1024;
 	pub const Version: RuntimeVersion = VERSION;
+	/// The prefix of the SS58 addresses of this chain. `frame_system` 2.0 has no `SS58Prefix`
+	/// item to expose it in the metadata, so wallets learn it from the `ss58Format` property of
+	/// the chain spec, and the node sets it as the default address format.
+	pub const SS58Prefix: u8 = 51;
 }
 
 // Configure FRAME pallets to include in runtime.
 
 impl frame_system::Trait for Runtime {
 	/// The basic call filter to use in dispatchable.
-	type BaseCallFilter = ();
+	type BaseCallFilter = BaseFilter;
 	/// The identifier used to distinguish between accounts.
 	type AccountId = AccountId;
 	/// The aggregated dispatch type that is available for extrinsics.
 	type Call = Call;
-	/// The lookup mechanism to get account ID from whatever is passed in dispatchers.
-	type Lookup = IdentityLookup<AccountId>;
+	/// The lookup mechanism to get account ID from whatever is passed in dispatchers. Accounts
+	/// can be given either by their ID or by their index.
+	type Lookup = Indices;
 	/// The index type for storing how many extrinsics an account has signed.
 	type Index = Index;
 	/// The index type for blocks.
@@ -192,15 +268,47 @@ impl frame_system::Trait for Runtime {
 	type SystemWeightInfo = ();
 }
 
+#[cfg(not(feature = "babe"))]
 impl pallet_aura::Trait for Runtime {
 	type AuthorityId = AuraId;
 }
 
+#[cfg(feature = "babe")]
+parameter_types! {
+	pub const EpochDuration: u64 = EPOCH_DURATION_IN_SLOTS;
+	pub const ExpectedBlockTime: u64 = MILLISECS_PER_BLOCK;
+}
+
+#[cfg(feature = "babe")]
+impl pallet_babe::Trait for Runtime {
+	type EpochDuration = EpochDuration;
+	type ExpectedBlockTime = ExpectedBlockTime;
+	/// Epochs are changed by the session pallet, so that they follow validator set changes.
+	type EpochChangeTrigger = pallet_babe::ExternalTrigger;
+
+	type KeyOwnerProofSystem = Historical;
+
+	type KeyOwnerProof = <Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(
+		KeyTypeId,
+		pallet_babe::AuthorityId,
+	)>>::Proof;
+
+	type KeyOwnerIdentification = <Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(
+		KeyTypeId,
+		pallet_babe::AuthorityId,
+	)>>::IdentificationTuple;
+
+	type HandleEquivocation =
+		pallet_babe::EquivocationHandler<Self::KeyOwnerIdentification, Offences>;
+
+	type WeightInfo = ();
+}
+
 impl pallet_grandpa::Trait for Runtime {
 	type Event = Event;
 	type Call = Call;
 
-	type KeyOwnerProofSystem = ();
+	type KeyOwnerProofSystem = Historical;
 
 	type KeyOwnerProof =
 		<Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(KeyTypeId, GrandpaId)>>::Proof;
@@ -210,7 +318,8 @@ impl pallet_grandpa::Trait for Runtime {
 		GrandpaId,
 	)>>::IdentificationTuple;
 
-	type HandleEquivocation = ();
+	type HandleEquivocation =
+		pallet_grandpa::EquivocationHandler<Self::KeyOwnerIdentification, Offences>;
 
 	type WeightInfo = ();
 }
@@ -222,11 +331,25 @@ parameter_types! {
 impl pallet_timestamp::Trait for Runtime {
 	/// A timestamp: milliseconds since the unix epoch.
 	type Moment = u64;
-	type OnTimestampSet = Aura;
+	type OnTimestampSet = Consensus;
 	type MinimumPeriod = MinimumPeriod;
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const IndexDeposit: Balance = 1 * UNITS;
+}
+
+impl pallet_indices::Trait for Runtime {
+	/// The type for recording indexing into the account enumeration.
+	type AccountIndex = AccountIndex;
+	type Currency = Balances;
+	/// The deposit reserved for claiming an index.
+	type Deposit = IndexDeposit;
+	type Event = Event;
+	type WeightInfo = ();
+}
+
 parameter_types! {
 	pub const ExistentialDeposit: u128 = 500;
 	pub const MaxLocks: u32 = 50;
@@ -238,22 +361,523 @@ impl pallet_balances::Trait for Runtime {
 	type Balance = Balance;
 	/// The ubiquitous event type.
 	type Event = Event;
-	type DustRemoval = ();
+	/// Dust of reaped accounts goes to the treasury.
+	type DustRemoval = Treasury;
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = System;
 	type WeightInfo = ();
 }
 
 parameter_types! {
-	pub const TransactionByteFee: Balance = 1;
+	pub const MinVestedTransfer: Balance = 100 * UNITS;
+}
+
+/// Vesting schedules lock balances with one of the `MaxLocks` balance locks of an account.
+impl pallet_vesting::Trait for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BlockNumberToBalance = ConvertInto;
+	/// The smallest amount `vested_transfer` can lock behind a new schedule.
+	type MinVestedTransfer = MinVestedTransfer;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const TransactionByteFee: Balance = 10 * MILLICENTS;
+	/// The portion of the normal block weight the fee adjustment aims at.
+	pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+	/// How fast the multiplier reacts to blocks above or below the target fullness.
+	pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(1, 100_000);
+	/// The multiplier never goes below this value, however empty the blocks are.
+	pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 1_000_000_000u128);
+	/// Share of the transaction fees going to the treasury, the rest goes to the block author.
+	pub const FeesToTreasury: Percent = Percent::from_percent(80);
+	/// Share of the tips going to the treasury, the rest goes to the block author.
+	pub const TipsToTreasury: Percent = Percent::from_percent(80);
 }
 
 impl pallet_transaction_payment::Trait for Runtime {
 	type Currency = Balances;
-	type OnTransactionPayment = ();
+	type OnTransactionPayment = DealWithFees;
 	type TransactionByteFee = TransactionByteFee;
-	type WeightToFee = IdentityFee<Balance>;
-	type FeeMultiplierUpdate = ();
+	type WeightToFee = WeightToFee;
+	type FeeMultiplierUpdate =
+		TargetedFeeAdjustment<Self, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
+}
+
+parameter_types! {
+	pub const UncleGenerations: BlockNumber = 0;
+}
+
+impl pallet_authorship::Trait for Runtime {
+	/// The block authoring pallet only knows the index of the slot author, the session maps it
+	/// back to an account.
+	type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Consensus>;
+	/// Uncles are not rewarded.
+	type UncleGenerations = UncleGenerations;
+	type FilterUncle = ();
+	/// Give era points to the block authors.
+	type EventHandler = Staking;
+}
+
+parameter_types! {
+	pub const Period: BlockNumber = SESSION_PERIOD;
+	pub const Offset: BlockNumber = 0;
+	pub const DisabledValidatorsThreshold: Perbill = Perbill::from_percent(17);
+}
+
+/// Sessions last `SESSION_PERIOD` blocks. Under BABE they end with its epochs, which last as long.
+#[cfg(not(feature = "babe"))]
+pub type SessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
+#[cfg(feature = "babe")]
+pub type SessionRotation = Babe;
+
+impl pallet_session::Trait for Runtime {
+	type Event = Event;
+	/// Validators are identified by their stash account.
+	type ValidatorId = <Self as frame_system::Trait>::AccountId;
+	type ValidatorIdOf = pallet_staking::StashOf<Self>;
+	type ShouldEndSession = SessionRotation;
+	type NextSessionRotation = SessionRotation;
+	/// The validator set of each new session is elected by staking, and noted in the
+	/// historical trie so that key ownership can be proven for past sessions.
+	type SessionManager = pallet_session::historical::NoteHistoricalRoot<Self, Staking>;
+	type SessionHandler = <opaque::SessionKeys as OpaqueKeys>::KeyTypeIdProviders;
+	type Keys = opaque::SessionKeys;
+	type DisabledValidatorsThreshold = DisabledValidatorsThreshold;
+	type WeightInfo = ();
+}
+
+impl pallet_session::historical::Trait for Runtime {
+	type FullIdentification = pallet_staking::Exposure<AccountId, Balance>;
+	type FullIdentificationOf = pallet_staking::ExposureOf<Runtime>;
+}
+
+pallet_staking_reward_curve::build! {
+	const REWARD_CURVE: PiecewiseLinear<'static> = curve!(
+		min_inflation: 0_025_000,
+		max_inflation: 0_100_000,
+		ideal_stake: 0_500_000,
+		falloff: 0_050_000,
+		max_piece_count: 40,
+		test_precision: 0_005_000,
+	);
+}
+
+parameter_types! {
+	pub const SessionsPerEra: SessionIndex = SESSIONS_PER_ERA;
+	/// Funds stay bonded for a week after `unbond`.
+	pub const BondingDuration: pallet_staking::EraIndex = 7 * DAYS / ERA_PERIOD;
+	/// Slashes are applied two days after the offence, leaving time to cancel them.
+	pub const SlashDeferDuration: pallet_staking::EraIndex = 2 * DAYS / ERA_PERIOD;
+	pub const RewardCurve: &'static PiecewiseLinear<'static> = &REWARD_CURVE;
+	/// The election solution is computed offchain during the last quarter of a session.
+	pub const ElectionLookahead: BlockNumber = SESSION_PERIOD / 4;
+	pub const MaxNominatorRewardedPerValidator: u32 = 64;
+	pub const MaxIterations: u32 = 10;
+	/// 0.05%. The higher the value, the more strict solution acceptance becomes.
+	pub MinSolutionScoreBump: Perbill = Perbill::from_rational_approximation(5u32, 10_000);
+	pub OffchainSolutionWeightLimit: Weight = MaximumExtrinsicWeight::get()
+		.saturating_sub(BlockExecutionWeight::get())
+		.saturating_sub(ExtrinsicBaseWeight::get());
+	pub const StakingUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+}
+
+impl pallet_staking::Trait for Runtime {
+	type Currency = Balances;
+	type UnixTime = Timestamp;
+	type CurrencyToVote = CurrencyToVoteHandler;
+	type RewardRemainder = Treasury;
+	type Event = Event;
+	/// Send the slashed funds to the treasury.
+	type Slash = Treasury;
+	/// Rewards are minted from the void.
+	type Reward = ();
+	type SessionsPerEra = SessionsPerEra;
+	type BondingDuration = BondingDuration;
+	type SlashDeferDuration = SlashDeferDuration;
+	/// A super-majority of the council can cancel the slash.
+	type SlashCancelOrigin = EnsureOneOf<
+		AccountId,
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<_3, _4, AccountId, CouncilCollective>,
+	>;
+	type SessionInterface = Self;
+	type RewardCurve = RewardCurve;
+	type NextNewSession = Session;
+	type ElectionLookahead = ElectionLookahead;
+	type Call = Call;
+	type MaxIterations = MaxIterations;
+	type MinSolutionScoreBump = MinSolutionScoreBump;
+	type MaxNominatorRewardedPerValidator = MaxNominatorRewardedPerValidator;
+	type UnsignedPriority = StakingUnsignedPriority;
+	type OffchainSolutionWeightLimit = OffchainSolutionWeightLimit;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub OffencesWeightSoftLimit: Weight = Perbill::from_percent(60) * MaximumBlockWeight::get();
+}
+
+impl pallet_offences::Trait for Runtime {
+	type Event = Event;
+	type IdentificationTuple = pallet_session::historical::IdentificationTuple<Self>;
+	/// Offenders are slashed by staking.
+	type OnOffenceHandler = Staking;
+	type WeightSoftLimit = OffencesWeightSoftLimit;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime where
+	Call: From<C>,
+{
+	type Extrinsic = UncheckedExtrinsic;
+	type OverarchingCall = Call;
+}
+
+parameter_types! {
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * MaximumBlockWeight::get();
+	pub const MaxScheduledPerBlock: u32 = 50;
+}
+
+impl pallet_scheduler::Trait for Runtime {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = EnsureRoot<AccountId>;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const CouncilMotionDuration: BlockNumber = 5 * DAYS;
+	pub const CouncilMaxProposals: u32 = 100;
+	pub const CouncilMaxMembers: u32 = 100;
+}
+
+type CouncilCollective = pallet_collective::Instance1;
+impl pallet_collective::Trait<CouncilCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = CouncilMotionDuration;
+	type MaxProposals = CouncilMaxProposals;
+	type MaxMembers = CouncilMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const CandidacyBond: Balance = 10 * UNITS;
+	pub const VotingBond: Balance = 1 * UNITS;
+	pub const TermDuration: BlockNumber = 7 * DAYS;
+	/// Must not exceed `CouncilMaxMembers`.
+	pub const DesiredMembers: u32 = 13;
+	pub const DesiredRunnersUp: u32 = 7;
+	pub const ElectionsPhragmenModuleId: LockIdentifier = *b"phrelect";
+}
+
+impl pallet_elections_phragmen::Trait for Runtime {
+	type Event = Event;
+	type ModuleId = ElectionsPhragmenModuleId;
+	type Currency = Balances;
+	type ChangeMembers = Council;
+	// NOTE: this implies that council's genesis members cannot be set directly and must come from
+	// this module.
+	type InitializeMembers = Council;
+	type CurrencyToVote = CurrencyToVoteHandler;
+	type CandidacyBond = CandidacyBond;
+	type VotingBond = VotingBond;
+	type LoserCandidate = Treasury;
+	type BadReport = Treasury;
+	type KickedMember = Treasury;
+	type DesiredMembers = DesiredMembers;
+	type DesiredRunnersUp = DesiredRunnersUp;
+	type TermDuration = TermDuration;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const TechnicalMotionDuration: BlockNumber = 5 * DAYS;
+	pub const TechnicalMaxProposals: u32 = 100;
+	pub const TechnicalMaxMembers: u32 = 100;
+}
+
+type TechnicalCollective = pallet_collective::Instance2;
+impl pallet_collective::Trait<TechnicalCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = TechnicalMotionDuration;
+	type MaxProposals = TechnicalMaxProposals;
+	type MaxMembers = TechnicalMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = ();
+}
+
+type EnsureRootOrHalfCouncil = EnsureOneOf<
+	AccountId,
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionMoreThan<_1, _2, AccountId, CouncilCollective>,
+>;
+
+impl pallet_membership::Trait<pallet_membership::Instance1> for Runtime {
+	type Event = Event;
+	type AddOrigin = EnsureRootOrHalfCouncil;
+	type RemoveOrigin = EnsureRootOrHalfCouncil;
+	type SwapOrigin = EnsureRootOrHalfCouncil;
+	type ResetOrigin = EnsureRootOrHalfCouncil;
+	type PrimeOrigin = EnsureRootOrHalfCouncil;
+	type MembershipInitialized = TechnicalCommittee;
+	type MembershipChanged = TechnicalCommittee;
+}
+
+parameter_types! {
+	pub const LaunchPeriod: BlockNumber = 7 * DAYS;
+	pub const VotingPeriod: BlockNumber = 7 * DAYS;
+	pub const FastTrackVotingPeriod: BlockNumber = 3 * HOURS;
+	pub const InstantAllowed: bool = true;
+	pub const MinimumDeposit: Balance = 100 * UNITS;
+	pub const EnactmentPeriod: BlockNumber = 8 * DAYS;
+	pub const CooloffPeriod: BlockNumber = 7 * DAYS;
+	pub const PreimageByteDeposit: Balance = 1 * CENTS;
+	pub const MaxVotes: u32 = 100;
+	pub const MaxProposals: u32 = 100;
+}
+
+impl pallet_democracy::Trait for Runtime {
+	type Proposal = Call;
+	type Event = Event;
+	type Currency = Balances;
+	type EnactmentPeriod = EnactmentPeriod;
+	type LaunchPeriod = LaunchPeriod;
+	type VotingPeriod = VotingPeriod;
+	type MinimumDeposit = MinimumDeposit;
+	/// A straight majority of the council can decide what their next motion is.
+	type ExternalOrigin =
+		pallet_collective::EnsureProportionAtLeast<_1, _2, AccountId, CouncilCollective>;
+	/// A super-majority can have the next scheduled referendum be a straight majority-carries vote.
+	type ExternalMajorityOrigin =
+		pallet_collective::EnsureProportionAtLeast<_3, _4, AccountId, CouncilCollective>;
+	/// A unanimous council can have the next scheduled referendum be a straight default-carries
+	/// (NTB) vote.
+	type ExternalDefaultOrigin =
+		pallet_collective::EnsureProportionAtLeast<_1, _1, AccountId, CouncilCollective>;
+	/// Two thirds of the technical committee can have an ExternalMajority/ExternalDefault vote
+	/// be tabled immediately and with a shorter voting/enactment period.
+	type FastTrackOrigin =
+		pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, TechnicalCollective>;
+	type InstantOrigin =
+		pallet_collective::EnsureProportionAtLeast<_1, _1, AccountId, TechnicalCollective>;
+	type InstantAllowed = InstantAllowed;
+	type FastTrackVotingPeriod = FastTrackVotingPeriod;
+	/// To cancel a proposal which has been passed, 2/3 of the council must agree to it.
+	type CancellationOrigin =
+		pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, CouncilCollective>;
+	/// Any single technical committee member may veto a coming council proposal, however they
+	/// can only do it once and it lasts only for the cooloff period.
+	type VetoOrigin = pallet_collective::EnsureMember<AccountId, TechnicalCollective>;
+	type CooloffPeriod = CooloffPeriod;
+	type PreimageByteDeposit = PreimageByteDeposit;
+	type OperationalPreimageOrigin = pallet_collective::EnsureMember<AccountId, CouncilCollective>;
+	type Slash = Treasury;
+	type Scheduler = Scheduler;
+	type PalletsOrigin = OriginCaller;
+	type MaxVotes = MaxVotes;
+	type WeightInfo = ();
+	type MaxProposals = MaxProposals;
+}
+
+parameter_types! {
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const ProposalBondMinimum: Balance = 1 * UNITS;
+	pub const SpendPeriod: BlockNumber = 1 * DAYS;
+	/// Share of the unspent funds burnt at the end of each spend period.
+	pub const Burn: Permill = Permill::from_percent(50);
+	pub const TipCountdown: BlockNumber = 1 * DAYS;
+	pub const TipFindersFee: Percent = Percent::from_percent(20);
+	pub const TipReportDepositBase: Balance = 1 * UNITS;
+	pub const DataDepositPerByte: Balance = 1 * CENTS;
+	pub const BountyDepositBase: Balance = 1 * UNITS;
+	pub const BountyDepositPayoutDelay: BlockNumber = 1 * DAYS;
+	pub const TreasuryModuleId: ModuleId = ModuleId(*b"py/trsry");
+	pub const BountyUpdatePeriod: BlockNumber = 14 * DAYS;
+	pub const MaximumReasonLength: u32 = 16384;
+	pub const BountyCuratorDeposit: Permill = Permill::from_percent(50);
+	pub const BountyValueMinimum: Balance = 5 * UNITS;
+}
+
+impl pallet_treasury::Trait for Runtime {
+	type ModuleId = TreasuryModuleId;
+	type Currency = Balances;
+	type ApproveOrigin = EnsureOneOf<
+		AccountId,
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<_3, _5, AccountId, CouncilCollective>,
+	>;
+	type RejectOrigin = EnsureOneOf<
+		AccountId,
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionMoreThan<_1, _2, AccountId, CouncilCollective>,
+	>;
+	type Tippers = Elections;
+	type TipCountdown = TipCountdown;
+	type TipFindersFee = TipFindersFee;
+	type TipReportDepositBase = TipReportDepositBase;
+	type DataDepositPerByte = DataDepositPerByte;
+	type Event = Event;
+	type OnSlash = Treasury;
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type SpendPeriod = SpendPeriod;
+	type Burn = Burn;
+	type BountyDepositBase = BountyDepositBase;
+	type BountyDepositPayoutDelay = BountyDepositPayoutDelay;
+	type BountyUpdatePeriod = BountyUpdatePeriod;
+	type BountyCuratorDeposit = BountyCuratorDeposit;
+	type BountyValueMinimum = BountyValueMinimum;
+	type MaximumReasonLength = MaximumReasonLength;
+	type BurnDestination = ();
+	type WeightInfo = ();
+}
+
+impl pallet_utility::Trait for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	// One storage item; key size is 32; value is size 4+4+16+32 bytes = 56 bytes.
+	pub const DepositBase: Balance = deposit(1, 88);
+	// Additional storage item size of 32 bytes.
+	pub const DepositFactor: Balance = deposit(0, 32);
+	pub const MaxSignatories: u16 = 100;
+}
+
+impl pallet_multisig::Trait for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type DepositBase = DepositBase;
+	type DepositFactor = DepositFactor;
+	type MaxSignatories = MaxSignatories;
+	type WeightInfo = ();
+}
+
+/// The kinds of calls a proxy account is allowed to make on behalf of its delegator.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug)]
+pub enum ProxyType {
+	/// Any call.
+	Any,
+	/// Any call that cannot move funds out of the account.
+	NonTransfer,
+	/// Governance calls only.
+	Governance,
+}
+
+impl Default for ProxyType {
+	fn default() -> Self {
+		Self::Any
+	}
+}
+
+impl InstanceFilter<Call> for ProxyType {
+	fn filter(&self, c: &Call) -> bool {
+		match self {
+			ProxyType::Any => true,
+			ProxyType::NonTransfer => !matches!(c,
+				Call::Balances(..) |
+				Call::Indices(pallet_indices::Call::transfer(..)) |
+				Call::Vesting(pallet_vesting::Call::vested_transfer(..)) |
+				Call::Assets(pallet_fungible_assets::Call::transfer(..)) |
+				Call::Assets(pallet_fungible_assets::Call::approve_transfer(..)) |
+				Call::Assets(pallet_fungible_assets::Call::transfer_approved(..)) |
+				Call::Nft(pallet_nft::Call::transfer(..)) |
+				// Recovered accounts could be drained through the rescuer.
+				Call::Recovery(pallet_recovery::Call::as_recovered(..))
+			),
+			ProxyType::Governance => matches!(c,
+				Call::Democracy(..) |
+				Call::Council(..) |
+				Call::TechnicalCommittee(..) |
+				Call::Elections(..) |
+				Call::Treasury(..) |
+				// The batched calls are still filtered against the proxy type.
+				Call::Utility(..)
+			),
+		}
+	}
+
+	fn is_superset(&self, o: &Self) -> bool {
+		match (self, o) {
+			(x, y) if x == y => true,
+			(ProxyType::Any, _) => true,
+			(_, ProxyType::Any) => false,
+			(ProxyType::NonTransfer, _) => true,
+			_ => false,
+		}
+	}
+}
+
+parameter_types! {
+	// One storage item; key size 32, value size 8.
+	pub const ProxyDepositBase: Balance = deposit(1, 8);
+	// Additional storage item size of 33 bytes.
+	pub const ProxyDepositFactor: Balance = deposit(0, 33);
+	pub const MaxProxies: u16 = 32;
+	pub const AnnouncementDepositBase: Balance = deposit(1, 8);
+	pub const AnnouncementDepositFactor: Balance = deposit(0, 66);
+	pub const MaxPending: u16 = 32;
+}
+
+impl pallet_proxy::Trait for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type ProxyType = ProxyType;
+	type ProxyDepositBase = ProxyDepositBase;
+	type ProxyDepositFactor = ProxyDepositFactor;
+	type MaxProxies = MaxProxies;
+	type WeightInfo = ();
+	type MaxPending = MaxPending;
+	type CallHasher = BlakeTwo256;
+	type AnnouncementDepositBase = AnnouncementDepositBase;
+	type AnnouncementDepositFactor = AnnouncementDepositFactor;
+}
+
+#[cfg(not(feature = "babe"))]
+parameter_types! {
+	pub const RandomnessRoundLength: BlockNumber = 10 * MINUTES;
+}
+
+#[cfg(not(feature = "babe"))]
+impl pallet_randomness_beacon::Trait for Runtime {
+	type Event = Event;
+	/// Validators take part in the beacon from their controller accounts.
+	type Participants = ValidatorControllers;
+	type RoundLength = RandomnessRoundLength;
+}
+
+parameter_types! {
+	pub const ConfigDepositBase: Balance = 5 * UNITS;
+	pub const FriendDepositFactor: Balance = 50 * CENTS;
+	pub const MaxFriends: u16 = 9;
+	pub const RecoveryDeposit: Balance = 5 * UNITS;
+}
+
+impl pallet_recovery::Trait for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	/// Reserved from the lost account when it sets up its recovery configuration.
+	type ConfigDepositBase = ConfigDepositBase;
+	type FriendDepositFactor = FriendDepositFactor;
+	type MaxFriends = MaxFriends;
+	/// Reserved from the rescuer for each recovery it initiates, and handed over to the lost
+	/// account if it closes the recovery.
+	type RecoveryDeposit = RecoveryDeposit;
 }
 
 impl pallet_sudo::Trait for Runtime {
@@ -261,33 +885,156 @@ impl pallet_sudo::Trait for Runtime {
 	type Call = Call;
 }
 
+parameter_types! {
+	/// Block production and the means to resume paused calls must keep working during an incident.
+	/// Once sudo is retired, root calls come from governance, dispatched directly or through the
+	/// scheduler, so pausing any of those pallets could leave nothing able to resume it.
+	pub UnpausablePallets: Vec<Vec<u8>> = vec![
+		b"System".to_vec(),
+		b"Timestamp".to_vec(),
+		b"Sudo".to_vec(),
+		b"SudoHandover".to_vec(),
+		b"Pause".to_vec(),
+		b"Scheduler".to_vec(),
+		b"Democracy".to_vec(),
+		b"Council".to_vec(),
+		b"TechnicalCommittee".to_vec(),
+		b"Elections".to_vec(),
+		b"TechnicalMembership".to_vec(),
+	];
+}
+
+impl pallet_pause::Trait for Runtime {
+	type Event = Event;
+	type PauseOrigin = EnsureRoot<AccountId>;
+	type UnpausablePallets = UnpausablePallets;
+	type Call = Call;
+}
+
+impl pallet_sudo_handover::Trait for Runtime {
+	type Event = Event;
+	/// Sudo can only retire once there is a council to take over.
+	type GovernanceReady = CouncilSeated;
+}
+
+parameter_types! {
+	pub const AssetDeposit: Balance = 100 * UNITS;
+	pub const AssetMetadataDepositBase: Balance = deposit(1, 68);
+	pub const AssetMetadataDepositPerByte: Balance = deposit(0, 1);
+	pub const AssetStringLimit: u32 = 50;
+}
+
+impl pallet_fungible_assets::Trait for Runtime {
+	type Event = Event;
+	/// Assets use the same units as the native currency.
+	type Balance = Balance;
+	type AssetId = AssetId;
+	type Currency = Balances;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = AssetMetadataDepositBase;
+	type MetadataDepositPerByte = AssetMetadataDepositPerByte;
+	type StringLimit = AssetStringLimit;
+}
+
+parameter_types! {
+	pub const CollectionDeposit: Balance = 10 * UNITS;
+	pub const ItemMetadataDepositBase: Balance = deposit(1, 0);
+	pub const ItemMetadataDepositPerByte: Balance = deposit(0, 1);
+	pub const ItemStringLimit: u32 = 256;
+}
+
+impl pallet_nft::Trait for Runtime {
+	type Event = Event;
+	type CollectionId = CollectionId;
+	type ItemId = ItemId;
+	type Currency = Balances;
+	type CollectionDeposit = CollectionDeposit;
+	type MetadataDepositBase = ItemMetadataDepositBase;
+	type MetadataDepositPerByte = ItemMetadataDepositPerByte;
+	type StringLimit = ItemStringLimit;
+}
+
 /// Configure the template pallet in pallets/template.
 impl pallet_template::Trait for Runtime {
 	type Event = Event;
 }
 
-// Create the runtime by composing the FRAME pallets that were previously configured.
-construct_runtime!(
-	pub enum Runtime where
-		Block = Block,
-		NodeBlock = opaque::Block,
-		UncheckedExtrinsic = UncheckedExtrinsic
-	{
-		System: frame_system::{Module, Call, Config, Storage, Event<T>},
-		RandomnessCollectiveFlip: pallet_randomness_collective_flip::{Module, Call, Storage},
-		Timestamp: pallet_timestamp::{Module, Call, Storage, Inherent},
-		Aura: pallet_aura::{Module, Config<T>, Inherent},
-		Grandpa: pallet_grandpa::{Module, Call, Storage, Config, Event},
-		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
-		TransactionPayment: pallet_transaction_payment::{Module, Storage},
-		Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>},
-		// Include the custom logic from the template pallet in the runtime.
-		TemplateModule: pallet_template::{Module, Call, Storage, Event<T>},
+// Create the runtime by composing the FRAME pallets that were previously configured, the source of
+// randomness and the pallet authoring blocks, which depend on the `babe` feature.
+macro_rules! construct_node_template_runtime {
+	({ $($randomness:tt)* } { $($consensus:tt)* }) => {
+		construct_runtime!(
+			pub enum Runtime where
+				Block = Block,
+				NodeBlock = opaque::Block,
+				UncheckedExtrinsic = UncheckedExtrinsic
+			{
+				System: frame_system::{Module, Call, Config, Storage, Event<T>},
+				$($randomness)*
+				Timestamp: pallet_timestamp::{Module, Call, Storage, Inherent},
+				$($consensus)*
+				Grandpa: pallet_grandpa::{Module, Call, Storage, Config, Event, ValidateUnsigned},
+				Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+				TransactionPayment: pallet_transaction_payment::{Module, Storage},
+				Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>},
+				// Include the custom logic from the template pallet in the runtime.
+				TemplateModule: pallet_template::{Module, Call, Storage, Event<T>},
+				Authorship: pallet_authorship::{Module, Call, Storage},
+				// Staking must come before Session so that the genesis validators are elected first.
+				Staking: pallet_staking::{Module, Call, Config<T>, Storage, Event<T>, ValidateUnsigned},
+				Session: pallet_session::{Module, Call, Storage, Event, Config<T>},
+				Historical: pallet_session_historical::{Module},
+				Offences: pallet_offences::{Module, Call, Storage, Event},
+				Scheduler: pallet_scheduler::{Module, Call, Storage, Event<T>},
+				Democracy: pallet_democracy::{Module, Call, Storage, Config, Event<T>},
+				Council: pallet_collective::<Instance1>::{Module, Call, Storage, Origin<T>, Event<T>, Config<T>},
+				TechnicalCommittee: pallet_collective::<Instance2>::{Module, Call, Storage, Origin<T>, Event<T>, Config<T>},
+				Elections: pallet_elections_phragmen::{Module, Call, Storage, Event<T>, Config<T>},
+				TechnicalMembership: pallet_membership::<Instance1>::{Module, Call, Storage, Event<T>, Config<T>},
+				SudoHandover: pallet_sudo_handover::{Module, Call, Storage, Event},
+				Treasury: pallet_treasury::{Module, Call, Storage, Config, Event<T>},
+				Pause: pallet_pause::{Module, Call, Storage, Event},
+				Indices: pallet_indices::{Module, Call, Storage, Config<T>, Event<T>},
+				Utility: pallet_utility::{Module, Call, Event},
+				Multisig: pallet_multisig::{Module, Call, Storage, Event<T>},
+				Proxy: pallet_proxy::{Module, Call, Storage, Event<T>},
+				Vesting: pallet_vesting::{Module, Call, Storage, Event<T>, Config<T>},
+				Assets: pallet_fungible_assets::{Module, Call, Storage, Event<T>, Config<T>},
+				Nft: pallet_nft::{Module, Call, Storage, Event<T>},
+				Recovery: pallet_recovery::{Module, Call, Storage, Event<T>},
+			}
+		);
 	}
+}
+
+#[cfg(not(feature = "babe"))]
+construct_node_template_runtime!(
+	{ RandomnessBeacon: pallet_randomness_beacon::{Module, Call, Storage, Event<T>}, }
+	{ Aura: pallet_aura::{Module, Config<T>, Inherent}, }
+);
+
+// BABE provides the randomness itself, so the beacon is left out.
+#[cfg(feature = "babe")]
+construct_node_template_runtime!(
+	{}
+	{ Babe: pallet_babe::{Module, Call, Storage, Config, Inherent, ValidateUnsigned}, }
 );
 
-/// The address format for describing accounts.
-pub type Address = AccountId;
+/// The pallet authoring blocks, which the timestamp and authorship pallets rely on.
+#[cfg(not(feature = "babe"))]
+pub type Consensus = Aura;
+#[cfg(feature = "babe")]
+pub type Consensus = Babe;
+
+/// The source of on-chain randomness: the commit-reveal beacon of the validators with Aura, or the
+/// VRF outputs of the block authors with BABE.
+#[cfg(not(feature = "babe"))]
+pub type RandomnessSource = RandomnessBeacon;
+#[cfg(feature = "babe")]
+pub type RandomnessSource = Babe;
+
+/// The address format for describing accounts, either by their ID or by their index.
+pub type Address = <Indices as StaticLookup>::Source;
 /// Block header type as expected by this runtime.
 pub type Header = generic::Header<BlockNumber, BlakeTwo256>;
 /// Block type as expected by this runtime.
@@ -361,7 +1108,7 @@ impl_runtime_apis! {
 		}
 
 		fn random_seed() -> <Block as BlockT>::Hash {
-			RandomnessCollectiveFlip::random_seed()
+			RandomnessSource::random_seed()
 		}
 	}
 
@@ -380,6 +1127,7 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[cfg(not(feature = "babe"))]
 	impl sp_consensus_aura::AuraApi<Block, AuraId> for Runtime {
 		fn slot_duration() -> u64 {
 			Aura::slot_duration()
@@ -390,6 +1138,49 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[cfg(feature = "babe")]
+	impl sp_consensus_babe::BabeApi<Block> for Runtime {
+		fn configuration() -> sp_consensus_babe::BabeGenesisConfiguration {
+			// The epoch configuration is part of the runtime rather than of the genesis storage,
+			// and is read by the node from here when it imports the genesis block.
+			sp_consensus_babe::BabeGenesisConfiguration {
+				slot_duration: Babe::slot_duration(),
+				epoch_length: EpochDuration::get(),
+				c: PRIMARY_PROBABILITY,
+				genesis_authorities: Babe::authorities(),
+				randomness: Babe::randomness(),
+				allowed_slots: sp_consensus_babe::AllowedSlots::PrimaryAndSecondaryPlainSlots,
+			}
+		}
+
+		fn current_epoch_start() -> sp_consensus_babe::SlotNumber {
+			Babe::current_epoch_start()
+		}
+
+		fn generate_key_ownership_proof(
+			_slot_number: sp_consensus_babe::SlotNumber,
+			authority_id: sp_consensus_babe::AuthorityId,
+		) -> Option<sp_consensus_babe::OpaqueKeyOwnershipProof> {
+			use codec::Encode;
+
+			Historical::prove((sp_consensus_babe::KEY_TYPE, authority_id))
+				.map(|p| p.encode())
+				.map(sp_consensus_babe::OpaqueKeyOwnershipProof::new)
+		}
+
+		fn submit_report_equivocation_unsigned_extrinsic(
+			equivocation_proof: sp_consensus_babe::EquivocationProof<<Block as BlockT>::Header>,
+			key_owner_proof: sp_consensus_babe::OpaqueKeyOwnershipProof,
+		) -> Option<()> {
+			let key_owner_proof = key_owner_proof.decode()?;
+
+			Babe::submit_unsigned_equivocation_report(
+				equivocation_proof,
+				key_owner_proof,
+			)
+		}
+	}
+
 	impl sp_session::SessionKeys<Block> for Runtime {
 		fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
 			opaque::SessionKeys::generate(seed)
@@ -408,23 +1199,29 @@ impl_runtime_apis! {
 		}
 
 		fn submit_report_equivocation_unsigned_extrinsic(
-			_equivocation_proof: fg_primitives::EquivocationProof<
+			equivocation_proof: fg_primitives::EquivocationProof<
 				<Block as BlockT>::Hash,
 				NumberFor<Block>,
 			>,
-			_key_owner_proof: fg_primitives::OpaqueKeyOwnershipProof,
+			key_owner_proof: fg_primitives::OpaqueKeyOwnershipProof,
 		) -> Option<()> {
-			None
+			let key_owner_proof = key_owner_proof.decode()?;
+
+			Grandpa::submit_unsigned_equivocation_report(
+				equivocation_proof,
+				key_owner_proof,
+			)
 		}
 
 		fn generate_key_ownership_proof(
 			_set_id: fg_primitives::SetId,
-			_authority_id: GrandpaId,
+			authority_id: GrandpaId,
 		) -> Option<fg_primitives::OpaqueKeyOwnershipProof> {
-			// NOTE: this is the only implementation possible since we've
-			// defined our key owner proof type as a bottom type (i.e. a type
-			// with no values).
-			None
+			use codec::Encode;
+
+			Historical::prove((fg_primitives::KEY_TYPE, authority_id))
+				.map(|p| p.encode())
+				.map(fg_primitives::OpaqueKeyOwnershipProof::new)
 		}
 	}
 
@@ -434,6 +1231,42 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl node_template_runtime_api::TreasuryApi<Block, AccountId, Balance> for Runtime {
+		fn account_id() -> AccountId {
+			Treasury::account_id()
+		}
+
+		fn balance() -> Balance {
+			Treasury::pot()
+		}
+	}
+
+	impl node_template_runtime_api::VestingApi<Block, AccountId, Balance> for Runtime {
+		fn locked(who: AccountId) -> Balance {
+			Vesting::vesting(who)
+				.map(|s| s.locked_at::<ConvertInto>(System::block_number()))
+				.unwrap_or_default()
+		}
+
+		fn vested(who: AccountId) -> Balance {
+			Vesting::vesting(who)
+				.map(|s| s.locked.saturating_sub(s.locked_at::<ConvertInto>(System::block_number())))
+				.unwrap_or_default()
+		}
+	}
+
+	impl node_template_runtime_api::AssetsApi<Block, AccountId, AssetId, Balance> for Runtime {
+		fn account_balances(who: AccountId) -> Vec<(AssetId, Balance)> {
+			Assets::account_balances(&who)
+		}
+	}
+
+	impl node_template_runtime_api::NftApi<Block, AccountId, CollectionId, ItemId> for Runtime {
+		fn owned(who: AccountId) -> Vec<(CollectionId, ItemId)> {
+			Nft::owned(&who)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
 		fn query_info(
 			uxt: <Block as BlockT>::Extrinsic,
@@ -443,6 +1276,13 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[cfg(feature = "try-runtime")]
+	impl node_template_runtime_api::TryRuntime<Block> for Runtime {
+		fn on_runtime_upgrade() -> Result<Weight, Vec<u8>> {
+			try_runtime::on_runtime_upgrade()
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(
@@ -478,3 +1318,6 @@ impl_runtime_apis! {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests;
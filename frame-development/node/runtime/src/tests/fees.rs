@@ -0,0 +1,100 @@
+use super::*;
+use crate::impls::WeightToFee;
+use codec::Encode;
+use frame_support::weights::{DispatchClass, WeightToFeePolynomial};
+use pallet_transaction_payment::NextFeeMultiplier;
+
+/// The weight available to normal extrinsics in a block.
+fn max_normal() -> Weight {
+	AvailableBlockRatio::get() * MaximumBlockWeight::get()
+}
+
+/// The fee `payment_queryInfo` would quote for a remark with the current multiplier.
+fn quoted_fee() -> Balance {
+	let xt = UncheckedExtrinsic::new_unsigned(Call::System(frame_system::Call::remark(vec![0; 32])));
+	let len = xt.encoded_size() as u32;
+	TransactionPayment::query_info(xt, len).partial_fee
+}
+
+/// Author `blocks` blocks whose normal extrinsics use `fullness` of the available weight, and
+/// return the multiplier and quoted fee after each of them.
+fn run_blocks(blocks: u32, fullness: Perbill) -> Vec<(Multiplier, Balance)> {
+	(0..blocks).map(|_| {
+		let number = System::block_number();
+		System::set_block_limits(fullness * max_normal(), 0);
+		assert_eq!(System::block_weight().get(DispatchClass::Normal), fullness * max_normal());
+
+		TransactionPayment::on_finalize(number);
+		System::set_block_number(number + 1);
+
+		(TransactionPayment::next_fee_multiplier(), quoted_fee())
+	}).collect()
+}
+
+#[test]
+fn full_blocks_raise_the_multiplier() {
+	new_test_ext().execute_with(|| {
+		let initial = (TransactionPayment::next_fee_multiplier(), quoted_fee());
+
+		let trajectory = run_blocks(20, Perbill::from_percent(100));
+
+		let mut previous = initial;
+		for step in trajectory {
+			assert!(step.0 > previous.0, "multiplier went from {:?} to {:?}", previous.0, step.0);
+			assert!(step.1 > previous.1, "fee went from {} to {}", previous.1, step.1);
+			previous = step;
+		}
+	});
+}
+
+#[test]
+fn empty_blocks_lower_the_multiplier() {
+	new_test_ext().execute_with(|| {
+		let initial = (TransactionPayment::next_fee_multiplier(), quoted_fee());
+
+		let trajectory = run_blocks(20, Perbill::zero());
+
+		let mut previous = initial;
+		for step in trajectory {
+			assert!(step.0 < previous.0, "multiplier went from {:?} to {:?}", previous.0, step.0);
+			assert!(step.1 < previous.1, "fee went from {} to {}", previous.1, step.1);
+			previous = step;
+		}
+	});
+}
+
+#[test]
+fn multiplier_stays_put_at_target_fullness() {
+	new_test_ext().execute_with(|| {
+		let initial = (TransactionPayment::next_fee_multiplier(), quoted_fee());
+		let target = Perbill::from_parts(
+			(TargetBlockFullness::get().deconstruct() / 1_000_000_000) as u32,
+		);
+
+		for step in run_blocks(20, target) {
+			assert_eq!(step, initial);
+		}
+	});
+}
+
+#[test]
+fn multiplier_never_goes_below_the_minimum() {
+	new_test_ext().execute_with(|| {
+		NextFeeMultiplier::put(MinimumMultiplier::get());
+
+		for (multiplier, _) in run_blocks(5, Perbill::zero()) {
+			assert_eq!(multiplier, MinimumMultiplier::get());
+		}
+	});
+}
+
+#[test]
+fn weight_to_fee_is_superlinear() {
+	// The base weight of an extrinsic costs about a cent.
+	let base_fee = WeightToFee::calc(&ExtrinsicBaseWeight::get());
+	assert!(base_fee >= CENTS && base_fee < 2 * CENTS, "base fee is {}", base_fee);
+
+	// Doubling the weight of a heavy extrinsic more than doubles its fee.
+	let heavy = MaximumExtrinsicWeight::get() / 2;
+	assert!(WeightToFee::calc(&(2 * heavy)) > 2 * WeightToFee::calc(&heavy));
+}
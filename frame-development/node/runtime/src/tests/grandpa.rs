@@ -0,0 +1,109 @@
+use super::*;
+use codec::Encode;
+use frame_support::{assert_ok, traits::KeyOwnerProofSystem};
+use sp_core::H256;
+use sp_finality_grandpa::{EquivocationProof, RoundNumber, SetId};
+use sp_keyring::Ed25519Keyring;
+
+/// Sign two prevotes from the same voter in the same round and wrap them into a proof.
+fn generate_equivocation_proof(
+	set_id: SetId,
+	vote1: (RoundNumber, H256, BlockNumber, &Ed25519Keyring),
+	vote2: (RoundNumber, H256, BlockNumber, &Ed25519Keyring),
+) -> EquivocationProof<H256, BlockNumber> {
+	let signed_prevote = |round, hash, number, keyring: &Ed25519Keyring| {
+		let prevote = finality_grandpa::Prevote {
+			target_hash: hash,
+			target_number: number,
+		};
+
+		let prevote_msg = finality_grandpa::Message::Prevote(prevote.clone());
+		let payload = sp_finality_grandpa::localized_payload(round, set_id, &prevote_msg);
+		let signed = keyring.sign(&payload).into();
+		(prevote, signed)
+	};
+
+	let (prevote1, signed1) = signed_prevote(vote1.0, vote1.1, vote1.2, vote1.3);
+	let (prevote2, signed2) = signed_prevote(vote2.0, vote2.1, vote2.2, vote2.3);
+
+	EquivocationProof::new(
+		set_id,
+		sp_finality_grandpa::Equivocation::Prevote(finality_grandpa::Equivocation {
+			round_number: vote1.0,
+			identity: vote1.3.public().into(),
+			first: (prevote1, signed1),
+			second: (prevote2, signed2),
+		}),
+	)
+}
+
+#[test]
+fn double_prevote_is_reported_and_slashed() {
+	new_test_ext().execute_with(|| {
+		start_era(1);
+
+		let offender = Ed25519Keyring::Alice;
+		let offender_key: GrandpaId = offender.public().into();
+		let stash = account("Alice//stash");
+		let balance = Balances::total_balance(&stash);
+
+		// Alice prevotes for two different blocks in the same round.
+		let set_id = Grandpa::current_set_id();
+		let equivocation_proof = generate_equivocation_proof(
+			set_id,
+			(1, H256::random(), 10, &offender),
+			(1, H256::random(), 10, &offender),
+		);
+
+		// The proof that the key belonged to a validator of the current session is what the
+		// node gets out of `generate_key_ownership_proof`.
+		let key_owner_proof = Historical::prove((fg_primitives::KEY_TYPE, &offender_key)).unwrap();
+		let opaque_proof = fg_primitives::OpaqueKeyOwnershipProof::new(key_owner_proof.encode());
+		assert_eq!(opaque_proof.decode(), Some(key_owner_proof.clone()));
+
+		assert_ok!(Grandpa::report_equivocation_unsigned(
+			Origin::none(),
+			equivocation_proof,
+			key_owner_proof,
+		));
+
+		assert!(System::events().iter().any(|record| matches!(
+			record.event,
+			Event::pallet_offences(pallet_offences::Event::Offence(..))
+		)));
+
+		// The slash is deferred, so the offender keeps its funds for now.
+		assert_eq!(Balances::total_balance(&stash), balance);
+
+		let active_era = Staking::active_era().unwrap().index;
+		start_era(active_era + SlashDeferDuration::get());
+
+		assert!(Balances::total_balance(&stash) < balance);
+	});
+}
+
+#[test]
+fn invalid_equivocation_is_rejected() {
+	new_test_ext().execute_with(|| {
+		start_era(1);
+
+		let offender = Ed25519Keyring::Alice;
+		let offender_key: GrandpaId = offender.public().into();
+
+		// Voting twice for the same block is not an equivocation.
+		let hash = H256::random();
+		let set_id = Grandpa::current_set_id();
+		let equivocation_proof = generate_equivocation_proof(
+			set_id,
+			(1, hash, 10, &offender),
+			(1, hash, 10, &offender),
+		);
+		let key_owner_proof = Historical::prove((fg_primitives::KEY_TYPE, &offender_key)).unwrap();
+
+		assert!(Grandpa::report_equivocation_unsigned(
+			Origin::none(),
+			equivocation_proof,
+			key_owner_proof,
+		).is_err());
+	});
+}
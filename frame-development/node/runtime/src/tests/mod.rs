@@ -0,0 +1,157 @@
+//! Tests running against the real runtime configuration.
+
+use crate::*;
+use frame_support::traits::{OnFinalize, OnInitialize};
+use sp_core::sr25519;
+use sp_runtime::traits::Header as HeaderT;
+
+mod fees;
+mod grandpa;
+mod pause;
+mod proxy;
+#[cfg(not(feature = "babe"))]
+mod randomness;
+mod recovery;
+mod sudo_handover;
+mod treasury;
+mod vesting;
+
+pub use crate::genesis::{get_from_seed, ENDOWMENT, STASH};
+
+/// Part of Ferdie's stash that is liquid from genesis, the rest vesting from `VESTING_START` on.
+pub const VESTING_LIQUID: Balance = ENDOWMENT / 4;
+/// Block at which the vesting of Ferdie's stash starts.
+pub const VESTING_START: BlockNumber = 10;
+/// Number of blocks over which Ferdie's stash vests.
+pub const VESTING_LENGTH: BlockNumber = 100;
+
+/// Generate an account ID from seed.
+pub fn account(seed: &str) -> AccountId {
+	<Signature as Verify>::Signer::from(get_from_seed::<sr25519::Public>(seed)).into_account()
+}
+
+/// The session keys of a validator, generated from its seed.
+#[cfg(not(feature = "babe"))]
+pub fn session_keys(seed: &str) -> opaque::SessionKeys {
+	opaque::SessionKeys {
+		aura: get_from_seed::<AuraId>(seed),
+		grandpa: get_from_seed::<GrandpaId>(seed),
+	}
+}
+
+/// The session keys of a validator, generated from its seed.
+#[cfg(feature = "babe")]
+pub fn session_keys(seed: &str) -> opaque::SessionKeys {
+	opaque::SessionKeys {
+		babe: get_from_seed::<pallet_babe::AuthorityId>(seed),
+		grandpa: get_from_seed::<GrandpaId>(seed),
+	}
+}
+
+/// Build the genesis storage of a chain validated by Alice and Bob, with every well-known
+/// account endowed and Ferdie's stash vesting.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let validators = ["Alice", "Bob"];
+	let endowed = ["Alice", "Bob", "Charlie", "Dave", "Eve", "Ferdie"];
+
+	let mut ext: sp_io::TestExternalities = GenesisConfig {
+		frame_system: Some(SystemConfig {
+			code: vec![],
+			changes_trie_config: Default::default(),
+		}),
+		pallet_balances: Some(BalancesConfig {
+			balances: endowed.iter()
+				.flat_map(|s| vec![account(s), account(&format!("{}//stash", s))])
+				.map(|k| (k, ENDOWMENT))
+				.collect(),
+		}),
+		#[cfg(not(feature = "babe"))]
+		pallet_aura: Some(AuraConfig {
+			authorities: vec![],
+		}),
+		#[cfg(feature = "babe")]
+		pallet_babe: Some(BabeConfig {
+			authorities: vec![],
+		}),
+		pallet_grandpa: Some(GrandpaConfig {
+			authorities: vec![],
+		}),
+		pallet_staking: Some(StakingConfig {
+			validator_count: validators.len() as u32,
+			minimum_validator_count: 1,
+			stakers: validators.iter().map(|s| {
+				(account(&format!("{}//stash", s)), account(s), STASH, StakerStatus::Validator)
+			}).collect(),
+			// Nobody is invulnerable, so that offences are actually slashed.
+			invulnerables: vec![],
+			slash_reward_fraction: Perbill::from_percent(10),
+			.. Default::default()
+		}),
+		pallet_session: Some(SessionConfig {
+			keys: validators.iter().map(|s| {
+				let stash = account(&format!("{}//stash", s));
+				(stash.clone(), stash, session_keys(s))
+			}).collect(),
+		}),
+		pallet_democracy: Some(DemocracyConfig::default()),
+		pallet_collective_Instance1: Some(CouncilConfig::default()),
+		pallet_collective_Instance2: Some(TechnicalCommitteeConfig::default()),
+		pallet_elections_phragmen: Some(ElectionsConfig {
+			members: validators.iter().map(|s| (account(s), STASH)).collect(),
+		}),
+		pallet_membership_Instance1: Some(TechnicalMembershipConfig {
+			members: validators.iter().map(|s| account(s)).collect(),
+			phantom: Default::default(),
+		}),
+		pallet_sudo: Some(SudoConfig {
+			key: account("Alice"),
+		}),
+		pallet_treasury: Some(Default::default()),
+		pallet_indices: Some(IndicesConfig {
+			indices: vec![],
+		}),
+		pallet_fungible_assets: Some(AssetsConfig {
+			assets: vec![],
+			balances: vec![],
+		}),
+		pallet_vesting: Some(VestingConfig {
+			vesting: vec![
+				(account("Ferdie//stash"), VESTING_START, VESTING_LENGTH, VESTING_LIQUID),
+			],
+		}),
+	}.build_storage().unwrap().into();
+
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Author blocks until `session_index` starts, rotating the session on every block.
+pub fn start_session(session_index: SessionIndex) {
+	for _ in Session::current_index()..session_index {
+		let number = System::block_number();
+		Grandpa::on_finalize(number);
+		let parent_hash = System::finalize().hash();
+
+		System::initialize(
+			&(number + 1),
+			&parent_hash,
+			&Default::default(),
+			&Default::default(),
+			Default::default(),
+		);
+		// Block authoring pallets only accept one block per slot.
+		Timestamp::set_timestamp(System::block_number() as u64 * SLOT_DURATION);
+
+		System::on_initialize(System::block_number());
+		Session::rotate_session();
+		Grandpa::on_initialize(System::block_number());
+	}
+	assert_eq!(Session::current_index(), session_index);
+}
+
+/// Rotate sessions until `era` becomes the active staking era.
+pub fn start_era(era: pallet_staking::EraIndex) {
+	while Staking::active_era().map(|e| e.index).unwrap_or(0) < era {
+		start_session(Session::current_index() + 1);
+	}
+}
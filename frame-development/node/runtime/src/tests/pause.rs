@@ -0,0 +1,66 @@
+use super::*;
+use frame_support::{assert_noop, assert_ok, traits::Filter};
+use pallet_pause::Error;
+use sp_runtime::{DispatchError, traits::Dispatchable};
+
+fn transfer_to(dest: &str, value: Balance) -> Call {
+	Call::Balances(pallet_balances::Call::transfer(account(dest).into(), value))
+}
+
+#[test]
+fn paused_calls_are_rejected_by_the_base_filter() {
+	new_test_ext().execute_with(|| {
+		let balance = Balances::free_balance(&account("Eve"));
+		assert_ok!(Pause::pause_call(Origin::root(), b"Balances".to_vec(), b"transfer".to_vec()));
+
+		assert!(!BaseFilter::filter(&transfer_to("Eve", 1_000)));
+		assert_noop!(
+			transfer_to("Eve", 1_000).dispatch(Origin::signed(account("Charlie"))),
+			DispatchError::BadOrigin,
+		);
+		// Other calls of the pallet go through.
+		assert_ok!(Call::Balances(pallet_balances::Call::transfer_keep_alive(
+			account("Eve").into(),
+			1_000,
+		)).dispatch(Origin::signed(account("Charlie"))));
+
+		assert_ok!(Pause::resume_call(Origin::root(), b"Balances".to_vec(), b"transfer".to_vec()));
+		assert_ok!(transfer_to("Eve", 1_000).dispatch(Origin::signed(account("Charlie"))));
+		assert_eq!(Balances::free_balance(&account("Eve")), balance + 2_000);
+	});
+}
+
+#[test]
+fn only_pallets_and_calls_of_the_runtime_can_be_paused() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Pause::pause_pallet(Origin::root(), b"Balance".to_vec()),
+			Error::<Runtime>::UnknownPallet,
+		);
+		// Pallets without calls cannot be paused either.
+		assert_noop!(
+			Pause::pause_pallet(Origin::root(), b"TransactionPayment".to_vec()),
+			Error::<Runtime>::UnknownPallet,
+		);
+		assert_noop!(
+			Pause::pause_call(Origin::root(), b"Balances".to_vec(), b"Transfer".to_vec()),
+			Error::<Runtime>::UnknownCall,
+		);
+	});
+}
+
+#[test]
+fn governance_cannot_be_paused_after_the_sudo_handover() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SudoHandover::retire_sudo(Origin::root()));
+
+		assert_noop!(
+			Pause::pause_pallet(Origin::root(), b"Democracy".to_vec()),
+			Error::<Runtime>::Unpausable,
+		);
+		assert_noop!(
+			Pause::pause_call(Origin::root(), b"Scheduler".to_vec(), b"cancel".to_vec()),
+			Error::<Runtime>::Unpausable,
+		);
+	});
+}
@@ -0,0 +1,111 @@
+use super::*;
+use frame_support::assert_ok;
+
+fn transfer_to(dest: &str, value: Balance) -> Call {
+	Call::Balances(pallet_balances::Call::transfer(account(dest).into(), value))
+}
+
+#[test]
+fn non_transfer_proxy_cannot_move_funds() {
+	new_test_ext().execute_with(|| {
+		let (real, delegate) = (account("Charlie"), account("Dave"));
+		assert_ok!(Proxy::add_proxy(
+			Origin::signed(real.clone()),
+			delegate.clone(),
+			ProxyType::NonTransfer,
+			0,
+		));
+
+		let balance = Balances::free_balance(&account("Eve"));
+		assert_ok!(Proxy::proxy(
+			Origin::signed(delegate.clone()),
+			real.clone(),
+			None,
+			Box::new(transfer_to("Eve", 1_000)),
+		));
+		assert_eq!(Balances::free_balance(&account("Eve")), balance);
+
+		// Nor can it hide the transfer in a batch.
+		assert_ok!(Proxy::proxy(
+			Origin::signed(delegate),
+			real,
+			None,
+			Box::new(Call::Utility(pallet_utility::Call::batch(vec![transfer_to("Eve", 1_000)]))),
+		));
+		assert_eq!(Balances::free_balance(&account("Eve")), balance);
+	});
+}
+
+#[test]
+fn governance_proxy_can_batch_governance_calls_only() {
+	new_test_ext().execute_with(|| {
+		let (real, delegate) = (account("Charlie"), account("Dave"));
+		assert_ok!(Proxy::add_proxy(
+			Origin::signed(real.clone()),
+			delegate.clone(),
+			ProxyType::Governance,
+			0,
+		));
+
+		let balance = Balances::free_balance(&account("Eve"));
+		let propose_spend = Call::Treasury(pallet_treasury::Call::propose_spend(
+			1_000,
+			account("Eve").into(),
+		));
+		assert_ok!(Proxy::proxy(
+			Origin::signed(delegate),
+			real,
+			None,
+			Box::new(Call::Utility(pallet_utility::Call::batch(vec![
+				propose_spend,
+				transfer_to("Eve", 1_000),
+			]))),
+		));
+		// The proposal goes through and the batch is interrupted at the transfer.
+		assert_eq!(Treasury::proposal_count(), 1);
+		assert_eq!(Balances::free_balance(&account("Eve")), balance);
+	});
+}
+
+#[test]
+fn any_proxy_can_move_funds() {
+	new_test_ext().execute_with(|| {
+		let (real, delegate) = (account("Charlie"), account("Dave"));
+		assert_ok!(Proxy::add_proxy(
+			Origin::signed(real.clone()),
+			delegate.clone(),
+			ProxyType::Any,
+			0,
+		));
+
+		let balance = Balances::free_balance(&account("Eve"));
+		assert_ok!(Proxy::proxy(
+			Origin::signed(delegate),
+			real,
+			None,
+			Box::new(transfer_to("Eve", 1_000)),
+		));
+		assert_eq!(Balances::free_balance(&account("Eve")), balance + 1_000);
+	});
+}
+
+#[test]
+fn batches_respect_paused_calls() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pause::pause_call(Origin::root(), b"Balances".to_vec(), b"transfer".to_vec()));
+
+		let balance = Balances::free_balance(&account("Eve"));
+		assert!(Utility::batch_all(
+			Origin::signed(account("Charlie")),
+			vec![transfer_to("Eve", 1_000)],
+		).is_err());
+		assert_eq!(Balances::free_balance(&account("Eve")), balance);
+
+		// A plain batch is interrupted at the paused call.
+		assert_ok!(Utility::batch(
+			Origin::signed(account("Charlie")),
+			vec![transfer_to("Eve", 1_000)],
+		));
+		assert_eq!(Balances::free_balance(&account("Eve")), balance);
+	});
+}
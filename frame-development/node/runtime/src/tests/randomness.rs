@@ -0,0 +1,57 @@
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use pallet_randomness_beacon::Error;
+
+/// Initialize blocks until the next randomness round starts.
+fn next_round() {
+	let round = RandomnessBeacon::current_round();
+	while RandomnessBeacon::current_round() == round {
+		System::set_block_number(System::block_number() + 1);
+		RandomnessBeacon::on_initialize(System::block_number());
+	}
+}
+
+#[test]
+fn validators_take_part_from_their_controllers() {
+	new_test_ext().execute_with(|| {
+		let commitment = |who: &str| RandomnessBeacon::commitment_of(&account(who), &[0; 32]);
+
+		assert_ok!(RandomnessBeacon::commit(Origin::signed(account("Alice")), commitment("Alice")));
+		assert_noop!(
+			RandomnessBeacon::commit(Origin::signed(account("Charlie")), commitment("Charlie")),
+			Error::<Runtime>::NotParticipant,
+		);
+		assert_noop!(
+			RandomnessBeacon::commit(
+				Origin::signed(account("Bob//stash")),
+				commitment("Bob//stash"),
+			),
+			Error::<Runtime>::NotParticipant,
+		);
+	});
+}
+
+#[test]
+fn block_builder_seed_follows_the_beacon() {
+	new_test_ext().execute_with(|| {
+		let seed = <Runtime as sp_block_builder::runtime_decl_for_BlockBuilder::BlockBuilder<Block>>
+			::random_seed;
+
+		for (who, secret) in &[("Alice", [1; 32]), ("Bob", [2; 32])] {
+			let commitment = RandomnessBeacon::commitment_of(&account(who), secret);
+			assert_ok!(RandomnessBeacon::commit(Origin::signed(account(who)), commitment));
+		}
+		next_round();
+		let before = seed();
+
+		for (who, secret) in &[("Alice", [1; 32]), ("Bob", [2; 32])] {
+			assert_ok!(RandomnessBeacon::reveal(Origin::signed(account(who)), *secret));
+		}
+		// Revealing only changes the seed once the next round starts.
+		assert_eq!(seed(), before);
+
+		next_round();
+		assert_ne!(seed(), before);
+		assert_eq!(seed(), RandomnessBeacon::random_seed());
+	});
+}
@@ -0,0 +1,119 @@
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use pallet_recovery::Error;
+use sp_runtime::DispatchResult;
+
+/// The delay between the initiation of a recovery and the earliest claim.
+const DELAY: BlockNumber = 10;
+
+fn transfer_to(dest: &str, value: Balance) -> Call {
+	Call::Balances(pallet_balances::Call::transfer(account(dest).into(), value))
+}
+
+/// Let Ferdie be recovered by two out of Bob, Charlie and Dave, and have Eve initiate the
+/// recovery.
+fn set_up_recovery() {
+	let mut friends = vec![account("Bob"), account("Charlie"), account("Dave")];
+	friends.sort();
+	assert_ok!(Recovery::create_recovery(Origin::signed(account("Ferdie")), friends, 2, DELAY));
+	assert_ok!(Recovery::initiate_recovery(Origin::signed(account("Eve")), account("Ferdie")));
+}
+
+fn vouch(friend: &str) -> DispatchResult {
+	Recovery::vouch_recovery(Origin::signed(account(friend)), account("Ferdie"), account("Eve"))
+}
+
+#[test]
+fn rescuer_recovers_the_account_with_enough_vouches() {
+	new_test_ext().execute_with(|| {
+		set_up_recovery();
+		let deposit = ConfigDepositBase::get() + 3 * FriendDepositFactor::get();
+		assert_eq!(Balances::reserved_balance(&account("Ferdie")), deposit);
+		assert_eq!(Balances::reserved_balance(&account("Eve")), RecoveryDeposit::get());
+
+		assert_ok!(vouch("Bob"));
+		assert_ok!(vouch("Charlie"));
+
+		System::set_block_number(System::block_number() + DELAY);
+		assert_ok!(Recovery::claim_recovery(Origin::signed(account("Eve")), account("Ferdie")));
+		assert_eq!(Recovery::proxy(account("Eve")), Some(account("Ferdie")));
+
+		// Eve now acts on behalf of Ferdie.
+		let balance = Balances::free_balance(&account("Eve"));
+		assert_ok!(Recovery::as_recovered(
+			Origin::signed(account("Eve")),
+			account("Ferdie"),
+			Box::new(transfer_to("Eve", 1_000)),
+		));
+		assert_eq!(Balances::free_balance(&account("Eve")), balance + 1_000);
+
+		// And winds the recovery down, which hands Eve's deposit over to Ferdie and returns
+		// Ferdie's own.
+		let close = Call::Recovery(pallet_recovery::Call::close_recovery(account("Eve")));
+		assert_ok!(Recovery::as_recovered(
+			Origin::signed(account("Eve")),
+			account("Ferdie"),
+			Box::new(close),
+		));
+		let remove = Call::Recovery(pallet_recovery::Call::remove_recovery());
+		assert_ok!(Recovery::as_recovered(
+			Origin::signed(account("Eve")),
+			account("Ferdie"),
+			Box::new(remove),
+		));
+		assert_eq!(Balances::reserved_balance(&account("Ferdie")), 0);
+		assert_eq!(Balances::reserved_balance(&account("Eve")), 0);
+	});
+}
+
+#[test]
+fn claim_waits_for_the_threshold_and_the_delay() {
+	new_test_ext().execute_with(|| {
+		set_up_recovery();
+		let claim = || Recovery::claim_recovery(Origin::signed(account("Eve")), account("Ferdie"));
+
+		assert_ok!(vouch("Bob"));
+		System::set_block_number(System::block_number() + DELAY);
+		assert_noop!(claim(), Error::<Runtime>::Threshold);
+
+		assert_ok!(vouch("Dave"));
+		assert_ok!(claim());
+	});
+}
+
+#[test]
+fn claim_before_the_delay_fails() {
+	new_test_ext().execute_with(|| {
+		set_up_recovery();
+		assert_ok!(vouch("Bob"));
+		assert_ok!(vouch("Charlie"));
+
+		System::set_block_number(System::block_number() + DELAY - 1);
+		assert_noop!(
+			Recovery::claim_recovery(Origin::signed(account("Eve")), account("Ferdie")),
+			Error::<Runtime>::DelayPeriod,
+		);
+	});
+}
+
+#[test]
+fn only_friends_can_vouch() {
+	new_test_ext().execute_with(|| {
+		set_up_recovery();
+		assert_noop!(vouch("Alice"), Error::<Runtime>::NotFriend);
+	});
+}
+
+#[test]
+fn lost_account_can_close_a_malicious_recovery() {
+	new_test_ext().execute_with(|| {
+		set_up_recovery();
+		let balance = Balances::free_balance(&account("Ferdie"));
+
+		assert_ok!(Recovery::close_recovery(Origin::signed(account("Ferdie")), account("Eve")));
+		assert_eq!(Balances::free_balance(&account("Ferdie")), balance + RecoveryDeposit::get());
+		assert_eq!(Balances::reserved_balance(&account("Eve")), 0);
+
+		assert_noop!(vouch("Bob"), Error::<Runtime>::NotStarted);
+	});
+}
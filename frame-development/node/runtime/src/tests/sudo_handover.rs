@@ -0,0 +1,38 @@
+use super::*;
+use frame_support::{assert_noop, assert_ok, storage::unhashed, traits::Filter};
+use sp_runtime::{DispatchError, traits::Dispatchable};
+
+fn sudo_remark() -> Call {
+	Call::Sudo(pallet_sudo::Call::sudo(Box::new(Call::System(frame_system::Call::remark(vec![])))))
+}
+
+#[test]
+fn retiring_sudo_kills_the_sudo_key() {
+	new_test_ext().execute_with(|| {
+		// `retire_sudo` builds the storage key of `pallet_sudo::Key` by hand.
+		let key = SudoHandover::sudo_key_storage_key();
+		assert_eq!(unhashed::get::<AccountId>(&key), Some(Sudo::key()));
+		assert_eq!(Sudo::key(), account("Alice"));
+
+		assert_ok!(SudoHandover::retire_sudo(Origin::root()));
+		assert!(!unhashed::exists(&key));
+		assert_ne!(Sudo::key(), account("Alice"));
+	});
+}
+
+#[test]
+fn sudo_calls_are_filtered_once_retired() {
+	new_test_ext().execute_with(|| {
+		assert!(BaseFilter::filter(&sudo_remark()));
+		assert_ok!(sudo_remark().dispatch(Origin::signed(account("Alice"))));
+
+		assert_ok!(SudoHandover::retire_sudo(Origin::root()));
+		// Even with a sudo key left in storage, the call never reaches `pallet_sudo`.
+		unhashed::put(&SudoHandover::sudo_key_storage_key(), &account("Alice"));
+		assert!(!BaseFilter::filter(&sudo_remark()));
+		assert_noop!(
+			sudo_remark().dispatch(Origin::signed(account("Alice"))),
+			DispatchError::BadOrigin,
+		);
+	});
+}
@@ -0,0 +1,53 @@
+use super::*;
+use crate::impls::DealWithFees;
+use frame_support::{assert_ok, traits::{Currency, OnUnbalanced}};
+use node_template_runtime_api::runtime_decl_for_TreasuryApi::TreasuryApi;
+
+#[test]
+fn fees_and_tips_are_shared_with_the_treasury() {
+	new_test_ext().execute_with(|| {
+		let pot = Treasury::pot();
+
+		let fees = Balances::issue(1_000);
+		let tips = Balances::issue(100);
+		DealWithFees::on_unbalanceds(vec![fees, tips].into_iter());
+
+		assert_eq!(Treasury::pot(), pot + 800 + 80);
+	});
+}
+
+#[test]
+fn dust_goes_to_the_treasury() {
+	new_test_ext().execute_with(|| {
+		let pot = Treasury::pot();
+		let dust = ExistentialDeposit::get() - 1;
+
+		// Charlie transfers everything but an amount below the existential deposit.
+		assert_ok!(Balances::transfer(
+			Origin::signed(account("Charlie")),
+			account("Dave").into(),
+			ENDOWMENT - dust,
+		));
+
+		assert_eq!(Balances::total_balance(&account("Charlie")), 0);
+		assert_eq!(Treasury::pot(), pot + dust);
+	});
+}
+
+#[test]
+fn runtime_api_reports_the_spendable_treasury_balance() {
+	new_test_ext().execute_with(|| {
+		let fees = Balances::issue(1_000);
+		DealWithFees::on_unbalanceds(vec![fees].into_iter());
+
+		let account_id = <Runtime as TreasuryApi<Block, AccountId, Balance>>::account_id();
+		let balance = <Runtime as TreasuryApi<Block, AccountId, Balance>>::balance();
+
+		assert_eq!(account_id, Treasury::account_id());
+		assert_eq!(balance, 800);
+		assert_eq!(
+			Balances::free_balance(&Treasury::account_id()),
+			800 + ExistentialDeposit::get(),
+		);
+	});
+}
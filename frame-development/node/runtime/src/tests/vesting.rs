@@ -0,0 +1,106 @@
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use node_template_runtime_api::runtime_decl_for_VestingApi::VestingApi;
+use pallet_vesting::VestingInfo;
+
+fn locked(who: &str) -> Balance {
+	<Runtime as VestingApi<Block, AccountId, Balance>>::locked(account(who))
+}
+
+fn vested(who: &str) -> Balance {
+	<Runtime as VestingApi<Block, AccountId, Balance>>::vested(account(who))
+}
+
+#[test]
+fn genesis_schedule_locks_all_but_the_liquid_part() {
+	new_test_ext().execute_with(|| {
+		let total = ENDOWMENT - VESTING_LIQUID;
+		assert_eq!(locked("Ferdie//stash"), total);
+		assert_eq!(vested("Ferdie//stash"), 0);
+
+		assert_noop!(
+			Balances::transfer(
+				Origin::signed(account("Ferdie//stash")),
+				account("Eve").into(),
+				VESTING_LIQUID + 1,
+			),
+			pallet_balances::Error::<Runtime>::LiquidityRestrictions,
+		);
+		assert_ok!(Balances::transfer(
+			Origin::signed(account("Ferdie//stash")),
+			account("Eve").into(),
+			VESTING_LIQUID,
+		));
+	});
+}
+
+#[test]
+fn vested_funds_are_released_by_vest() {
+	new_test_ext().execute_with(|| {
+		let total = ENDOWMENT - VESTING_LIQUID;
+		let per_block = total / VESTING_LENGTH as Balance;
+
+		System::set_block_number(VESTING_START + VESTING_LENGTH / 2);
+		let released = per_block * (VESTING_LENGTH / 2) as Balance;
+		assert_eq!(vested("Ferdie//stash"), released);
+		assert_eq!(locked("Ferdie//stash"), total - released);
+
+		// The balance lock lags behind the schedule until the account vests.
+		let transfer = |value| Balances::transfer(
+			Origin::signed(account("Ferdie//stash")),
+			account("Eve").into(),
+			value,
+		);
+		assert!(transfer(VESTING_LIQUID + released).is_err());
+		assert_ok!(Vesting::vest(Origin::signed(account("Ferdie//stash"))));
+		assert_ok!(transfer(VESTING_LIQUID + released));
+
+		// The genesis schedule releases `total / VESTING_LENGTH` per block, rounded down, so the
+		// remainder of the division is only released the block after the schedule ends.
+		System::set_block_number(VESTING_START + VESTING_LENGTH);
+		let remainder = total - per_block * VESTING_LENGTH as Balance;
+		assert_eq!(locked("Ferdie//stash"), remainder);
+		assert_eq!(vested("Ferdie//stash"), total - remainder);
+
+		System::set_block_number(VESTING_START + VESTING_LENGTH + 1);
+		assert_eq!(locked("Ferdie//stash"), 0);
+		assert_eq!(vested("Ferdie//stash"), total);
+	});
+}
+
+#[test]
+fn vested_transfer_creates_a_schedule() {
+	new_test_ext().execute_with(|| {
+		let schedule = |locked| VestingInfo {
+			locked,
+			per_block: locked / 10,
+			starting_block: 5,
+		};
+
+		assert_noop!(
+			Vesting::vested_transfer(
+				Origin::signed(account("Charlie")),
+				account("Eve").into(),
+				schedule(MinVestedTransfer::get() - 1),
+			),
+			pallet_vesting::Error::<Runtime>::AmountLow,
+		);
+		assert_ok!(Vesting::vested_transfer(
+			Origin::signed(account("Charlie")),
+			account("Eve").into(),
+			schedule(MinVestedTransfer::get()),
+		));
+
+		assert_eq!(locked("Eve"), MinVestedTransfer::get());
+		System::set_block_number(7);
+		assert_eq!(vested("Eve"), 2 * MinVestedTransfer::get() / 10);
+	});
+}
+
+#[test]
+fn accounts_without_schedule_have_nothing_locked() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(locked("Charlie"), 0);
+		assert_eq!(vested("Charlie"), 0);
+	});
+}
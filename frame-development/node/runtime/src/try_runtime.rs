@@ -0,0 +1,30 @@
+//! The migrations of a runtime upgrade, run between two runs of the checks the pallets expose of
+//! their state, for the `TryRuntime` API.
+
+use sp_std::prelude::*;
+use frame_support::{traits::OnRuntimeUpgrade, weights::Weight};
+use crate::{AllModules, Assets, Nft, Runtime};
+
+/// The checks the pallets expose of their state.
+fn check_state() -> Result<(), &'static str> {
+	Assets::check_state()?;
+	Nft::check_state()
+}
+
+/// The message of a failing check, prefixed with `when` it failed.
+fn failed(when: &str, error: &str) -> Vec<u8> {
+	[when.as_bytes(), b": ", error.as_bytes()].concat()
+}
+
+/// Run the migrations as `Executive` does when it initializes the first block of a new runtime
+/// version, checking the state of the pallets before and after them.
+pub fn on_runtime_upgrade() -> Result<Weight, Vec<u8>> {
+	check_state().map_err(|e| failed("Before the migrations", e))?;
+
+	// System is not part of `AllModules`.
+	let weight = <frame_system::Module<Runtime> as OnRuntimeUpgrade>::on_runtime_upgrade()
+		.saturating_add(<AllModules as OnRuntimeUpgrade>::on_runtime_upgrade());
+
+	check_state().map_err(|e| failed("After the migrations", e))?;
+	Ok(weight)
+}